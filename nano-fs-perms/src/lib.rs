@@ -14,14 +14,69 @@
 //! assert_eq!(perms, 0o644);
 //! ```
 //!
+//! # Features
+//!
+//! * `std` (enabled by default)
+//!
+//!   This enables extensions that are only available with the Rust standard
+//!   library.
+//!
+//! * `fs`
+//!
+//!   Adds methods for applying permissions to files using [`std::fs`]. These
+//!   methods are only available on Unix platforms. This feature requires the
+//!   `std` feature and will automatically enable it if it is not already
+//!   enabled.
+//!
 //! [POSIX permission bits]: https://pubs.opengroup.org/onlinepubs/9699919799/basedefs/sys_stat.h.html
+//! [`std::fs`]: https://doc.rust-lang.org/std/fs/index.html
 
 #![no_std]
 
 #[cfg(feature = "std")]
 extern crate std;
 
-use core::{convert::TryFrom, fmt, ops};
+use core::{convert::TryFrom, fmt, ops, str, str::FromStr};
+
+/// Creates file access permissions from a literal, checked at compile time.
+///
+/// The literal is either a symbolic string as produced by the `Display`
+/// implementation of [`Perms`], e.g. `"rwxr-xr-x"`, or an integer, e.g.
+/// `0o755`. Integer literals follow the usual Rust rules, so a literal without
+/// a `0o` prefix is *not* interpreted as octal.
+///
+/// The result is a constant expression, so the macro may be used to define
+/// other constants.
+///
+/// # Examples
+///
+/// ```
+/// use nano_fs_perms::{perms, Perms};
+///
+/// const EXECUTABLE: Perms = perms!("rwxr-xr-x");
+///
+/// assert_eq!(EXECUTABLE, perms!(0o755));
+/// assert_eq!(perms!("rwsr-xr-T"), perms!(0o5754));
+/// ```
+///
+/// Malformed literals fail to compile:
+///
+/// ```compile_fail
+/// let _ = nano_fs_perms::perms!("rwxr-xr-q");
+/// ```
+///
+/// ```compile_fail
+/// let _ = nano_fs_perms::perms!(0o10000);
+/// ```
+///
+/// [`Perms`]: struct.Perms.html
+#[macro_export]
+macro_rules! perms {
+    ($lit:literal) => {{
+        const PERMS: $crate::Perms = $crate::Perms::__from_literal(::core::stringify!($lit));
+        PERMS
+    }};
+}
 
 /// File access permissions.
 ///
@@ -57,6 +112,16 @@ use core::{convert::TryFrom, fmt, ops};
 /// let perms = Perms::try_from(0o755).unwrap();
 /// assert_eq!(perms.to_string(), "rwxr-xr-x");
 /// ```
+///
+/// Constructing file access permissions from strings:
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use nano_fs_perms::Perms;
+///
+/// let perms = Perms::try_from("rwxr-xr-x").unwrap();
+/// assert_eq!(perms, Perms::try_from("0755").unwrap());
+/// ```
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct Perms(u32);
 
@@ -66,6 +131,18 @@ impl From<Perms> for u32 {
     }
 }
 
+impl From<&'_ Perms> for u32 {
+    fn from(perms: &'_ Perms) -> Self {
+        perms.0
+    }
+}
+
+impl AsRef<u32> for Perms {
+    fn as_ref(&self) -> &u32 {
+        &self.0
+    }
+}
+
 /// The error type returned when a checked file access permissions type
 /// conversion fails.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -84,11 +161,147 @@ impl TryFrom<u32> for Perms {
     type Error = PermsTryFromError;
 
     fn try_from(value: u32) -> Result<Self, Self::Error> {
-        if value & !Perms::MASK.0 != 0 {
-            Err(PermsTryFromError(()))
-        } else {
-            Ok(Self(value))
+        Perms::with_mask(value, Perms::MASK.0)
+    }
+}
+
+/// The error type returned when parsing file access permissions from a
+/// string fails.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct PermsParseError(());
+
+impl fmt::Display for PermsParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid file access permissions syntax")
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for PermsParseError {}
+
+impl FromStr for Perms {
+    type Err = PermsParseError;
+
+    /// Parses file access permissions from their symbolic representation.
+    ///
+    /// This is the inverse of the `Display` implementation, e.g.
+    /// `"rwsr-xr-T"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_symbolic(s.as_bytes())
+            .map(Perms)
+            .ok_or(PermsParseError(()))
+    }
+}
+
+/// Parses the mode of a symbolic representation, e.g. `"rwsr-xr-T"`.
+///
+/// This is `const` so that it may be shared with the `perms!` macro.
+const fn parse_symbolic(s: &[u8]) -> Option<u32> {
+    if s.len() != 9 {
+        return None;
+    }
+
+    let mut mode = 0;
+    let mut i = 0;
+
+    while i < 3 {
+        let shift = 6 - 3 * i;
+        let (special, with_exec, without_exec) = match i {
+            0 => (0o4000, b's', b'S'),
+            1 => (0o2000, b's', b'S'),
+            _ => (0o1000, b't', b'T'),
+        };
+
+        match s[3 * i] {
+            b'r' => mode |= 0o4 << shift,
+            b'-' => (),
+            _ => return None,
+        }
+
+        match s[3 * i + 1] {
+            b'w' => mode |= 0o2 << shift,
+            b'-' => (),
+            _ => return None,
+        }
+
+        match s[3 * i + 2] {
+            b'x' => mode |= 0o1 << shift,
+            b'-' => (),
+            c if c == with_exec => mode |= 0o1 << shift | special,
+            c if c == without_exec => mode |= special,
+            _ => return None,
+        }
+
+        i += 1;
+    }
+
+    Some(mode)
+}
+
+/// Parses the mode of an integer literal as written in source, e.g. `0o644`.
+///
+/// Radix prefixes, digit separators, and type suffixes are supported.
+const fn parse_int_literal(s: &[u8]) -> Option<u32> {
+    let (radix, mut i) = if s.len() > 2 && s[0] == b'0' {
+        match s[1] {
+            b'o' => (8, 2),
+            b'x' => (16, 2),
+            b'b' => (2, 2),
+            _ => (10, 0),
+        }
+    } else {
+        (10, 0)
+    };
+
+    let mut mode: u32 = 0;
+    let mut digits = 0;
+
+    while i < s.len() {
+        let digit = match s[i] {
+            b'_' => {
+                i += 1;
+                continue;
+            }
+            b'u' | b'i' => break,
+            c @ b'0'..=b'9' => (c - b'0') as u32,
+            c @ b'a'..=b'f' => (c - b'a') as u32 + 10,
+            c @ b'A'..=b'F' => (c - b'A') as u32 + 10,
+            _ => return None,
+        };
+
+        if digit >= radix {
+            return None;
+        }
+
+        mode = mode * radix + digit;
+
+        if mode > Perms::MASK.0 {
+            return None;
         }
+
+        digits += 1;
+        i += 1;
+    }
+
+    if digits == 0 {
+        None
+    } else {
+        Some(mode)
+    }
+}
+
+impl TryFrom<&'_ str> for Perms {
+    type Error = PermsParseError;
+
+    /// Parses file access permissions from either their symbolic or octal
+    /// representation.
+    ///
+    /// Symbolic parsing is attempted first, falling back to octal parsing.
+    /// A symbolic representation is always exactly nine characters, none of
+    /// which are digits, so the two formats never collide: any shorter input
+    /// (e.g. `"644"`) is only ever interpreted as octal.
+    fn try_from(s: &'_ str) -> Result<Self, Self::Error> {
+        s.parse().or_else(|_| Self::from_octal_str(s))
     }
 }
 
@@ -126,7 +339,7 @@ impl Perms {
     /// **Value**: `0o700`
     ///
     /// **POSIX**: `S_IRWXU`
-    pub const OWNER_ALL: Self = Self(0o70);
+    pub const OWNER_ALL: Self = Self(0o700);
 
     /// The file's user group has read permission.
     ///
@@ -238,71 +451,1636 @@ impl Perms {
     pub fn is_valid(value: u32) -> bool {
         Self::try_from(value).is_ok()
     }
-}
 
-impl fmt::Display for Perms {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut repr = ["-"; 9];
+    /// Returns a builder for permissions, starting from [`NONE`].
+    ///
+    /// See [`PermsBuilder`].
+    ///
+    /// [`NONE`]: #associatedconstant.NONE
+    /// [`PermsBuilder`]: struct.PermsBuilder.html
+    pub const fn builder() -> PermsBuilder {
+        PermsBuilder(Perms::NONE)
+    }
+
+    /// Returns the permissions as a `u32`.
+    ///
+    /// This is equivalent to `u32::from`, but takes `self` by reference for
+    /// use in generic code.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_fs_perms::Perms;
+    ///
+    /// let perms = &Perms::OWNER_ALL;
+    ///
+    /// assert_eq!(perms.as_u32(), 0o700);
+    /// ```
+    pub const fn as_u32(&self) -> u32 {
+        self.0
+    }
 
-        if self & Perms::OWNER_READ == Perms::OWNER_READ {
-            repr[0] = "r";
+    /// Attempts to convert `value` to file access permissions, validating it
+    /// against `mask` rather than [`MASK`].
+    ///
+    /// This is an escape hatch for platforms which define mode bits beyond
+    /// the 12 POSIX permission bits. Bits outside of [`MASK`] are preserved
+    /// by the bitwise operators and by `u32::from`, but are otherwise
+    /// ignored: they are not shown by `Display` and they are dropped by
+    /// conversions to narrower representations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_fs_perms::Perms;
+    ///
+    /// const EXTENDED: u32 = 0o17777;
+    ///
+    /// let perms = Perms::with_mask(0o10644, EXTENDED).unwrap();
+    /// assert_eq!(u32::from(perms), 0o10644);
+    /// assert_eq!(perms.to_string(), "rw-r--r--");
+    ///
+    /// assert!(Perms::with_mask(0o20644, EXTENDED).is_err());
+    /// ```
+    ///
+    /// [`MASK`]: #associatedconstant.MASK
+    pub fn with_mask(value: u32, mask: u32) -> Result<Self, PermsTryFromError> {
+        if value & !mask != 0 {
+            Err(PermsTryFromError(()))
+        } else {
+            Ok(Self(value))
+        }
+    }
+
+    /// Attempts to parse file access permissions from a string of octal
+    /// digits, e.g. `"0755"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_fs_perms::Perms;
+    ///
+    /// let perms = Perms::from_octal_str("4755").unwrap();
+    /// assert_eq!(perms.to_string(), "rwsr-xr-x");
+    ///
+    /// assert!(Perms::from_octal_str("0o755").is_err());
+    /// assert!(Perms::from_octal_str("10000").is_err());
+    /// ```
+    pub fn from_octal_str(s: &str) -> Result<Self, PermsParseError> {
+        if s.is_empty() || !s.bytes().all(|c| (b'0'..=b'7').contains(&c)) {
+            return Err(PermsParseError(()));
         }
 
-        if self & Perms::OWNER_WRITE == Perms::OWNER_WRITE {
-            repr[1] = "w";
+        u32::from_str_radix(s, 8)
+            .ok()
+            .and_then(|value| Self::try_from(value).ok())
+            .ok_or(PermsParseError(()))
+    }
+
+    /// Parses file access permissions from a symbolic `chmod` mode made up
+    /// only of `=` clauses, e.g. `"u=rw,g=r,o=r"`.
+    ///
+    /// Unlike `chmod` the mode is applied to [`NONE`] rather than to existing
+    /// permissions, so any class which is not mentioned is left cleared.
+    /// Clauses are applied in order, so a later clause replaces the
+    /// permissions of an earlier one for the same class. An empty class list
+    /// is treated as `a`.
+    ///
+    /// As with `chmod`, `s` only applies to the `u` and `g` classes and `t`
+    /// only applies to the `o` class; otherwise they are ignored.
+    ///
+    /// Returns an error if a clause uses the `+` or `-` operators, as these
+    /// are meaningless without an existing set of permissions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::Perms;
+    ///
+    /// let perms = Perms::parse_absolute_symbolic("u=rw,g=r,o=r").unwrap();
+    /// assert_eq!(perms, Perms::try_from(0o644).unwrap());
+    ///
+    /// let perms = Perms::parse_absolute_symbolic("a=rx,u=rwxs").unwrap();
+    /// assert_eq!(perms, Perms::try_from(0o4755).unwrap());
+    ///
+    /// assert!(Perms::parse_absolute_symbolic("u+x").is_err());
+    /// ```
+    ///
+    /// [`NONE`]: #associatedconstant.NONE
+    pub fn parse_absolute_symbolic(spec: &str) -> Result<Self, PermsParseError> {
+        let mut mode = 0;
+
+        for clause in spec.split(',') {
+            let eq = clause.find('=').ok_or(PermsParseError(()))?;
+            let (who, perms) = (&clause[..eq], &clause[eq + 1..]);
+
+            let mut classes = [false; 3];
+
+            for c in who.bytes() {
+                match c {
+                    b'u' => classes[0] = true,
+                    b'g' => classes[1] = true,
+                    b'o' => classes[2] = true,
+                    b'a' => classes = [true; 3],
+                    _ => return Err(PermsParseError(())),
+                }
+            }
+
+            if who.is_empty() {
+                classes = [true; 3];
+            }
+
+            for (i, _) in classes.iter().enumerate().filter(|&(_, &set)| set) {
+                let shift = 6 - 3 * i as u32;
+                let special = 0o4000 >> i;
+
+                let mut bits = 0;
+
+                for c in perms.bytes() {
+                    match c {
+                        b'r' => bits |= 0o4 << shift,
+                        b'w' => bits |= 0o2 << shift,
+                        b'x' => bits |= 0o1 << shift,
+                        b's' if i < 2 => bits |= special,
+                        b't' if i == 2 => bits |= special,
+                        b's' | b't' => (),
+                        _ => return Err(PermsParseError(())),
+                    }
+                }
+
+                mode = mode & !(0o7 << shift | special) | bits;
+            }
         }
 
-        match (
-            self & Perms::OWNER_EXEC == Perms::OWNER_EXEC,
-            self & Perms::SET_UID == Perms::SET_UID,
-        ) {
-            (true, false) => repr[2] = "x",
-            (true, true) => repr[2] = "s",
-            (false, true) => repr[2] = "S",
-            (false, false) => (),
+        Ok(Self(mode))
+    }
+
+    /// Evaluates the literal passed to the [`perms!`] macro.
+    ///
+    /// [`perms!`]: macro.perms.html
+    #[doc(hidden)]
+    pub const fn __from_literal(lit: &str) -> Self {
+        let lit = lit.as_bytes();
+
+        let mode = if lit.len() >= 2 && lit[0] == b'"' && lit[lit.len() - 1] == b'"' {
+            let (_, symbolic) = lit.split_at(1);
+            let (symbolic, _) = symbolic.split_at(symbolic.len() - 1);
+
+            parse_symbolic(symbolic)
+        } else {
+            parse_int_literal(lit)
+        };
+
+        match mode {
+            Some(mode) => Self(mode),
+            None => panic!("invalid file access permissions literal"),
         }
+    }
 
-        if self & Perms::GROUP_READ == Perms::GROUP_READ {
-            repr[3] = "r";
+    /// Returns the permission bit granting `op` to `class`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_fs_perms::{Class, Op, Perms};
+    ///
+    /// assert_eq!(Perms::bit(Class::Group, Op::Write), Perms::GROUP_WRITE);
+    /// ```
+    pub fn bit(class: Class, op: Op) -> Self {
+        let op = match op {
+            Op::Read => 0o4,
+            Op::Write => 0o2,
+            Op::Exec => 0o1,
+        };
+
+        match class {
+            Class::Owner => Self(op << 6),
+            Class::Group => Self(op << 3),
+            Class::Others => Self(op),
         }
+    }
+
+    /// Returns `true` if `class` is permitted to perform `op`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::{Class, Op, Perms};
+    ///
+    /// let perms = Perms::try_from(0o754).unwrap();
+    ///
+    /// assert!(perms.allows(Class::Owner, Op::Exec));
+    /// assert!(perms.allows(Class::Group, Op::Read));
+    /// assert!(!perms.allows(Class::Others, Op::Write));
+    /// ```
+    pub fn allows(self, class: Class, op: Op) -> bool {
+        let bit = Self::bit(class, op);
+
+        self & bit == bit
+    }
+
+    /// Computes the permissions of a new child created within a directory
+    /// with default permissions `parent_default`.
+    ///
+    /// The following rule is applied:
+    ///
+    /// * Read and write bits are granted only where both the requested
+    ///   permissions (`self`) and `parent_default` grant them.
+    /// * Execute/search bits are taken from `parent_default` for directories
+    ///   (`is_dir`), so that the tree remains traversable. Files are never
+    ///   granted execute bits, even where both the requested permissions and
+    ///   `parent_default` grant them, so a file must be made executable
+    ///   explicitly.
+    /// * `SET_GID` is inherited from `parent_default` by directories only.
+    ///   `SET_UID` and `STICKY_BIT` are never inherited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::Perms;
+    ///
+    /// let parent_default = Perms::try_from(0o2775).unwrap();
+    ///
+    /// let file = Perms::try_from(0o666).unwrap().inherit_from(parent_default, false);
+    /// assert_eq!(u32::from(file), 0o664);
+    ///
+    /// let file = Perms::try_from(0o755).unwrap().inherit_from(parent_default, false);
+    /// assert_eq!(u32::from(file), 0o644);
+    ///
+    /// let dir = Perms::try_from(0o666).unwrap().inherit_from(parent_default, true);
+    /// assert_eq!(u32::from(dir), 0o2775);
+    /// ```
+    pub fn inherit_from(self, parent_default: Perms, is_dir: bool) -> Perms {
+        const READ_WRITE: Perms = Perms(0o666);
+        const EXEC: Perms = Perms(0o111);
 
-        if self & Perms::GROUP_WRITE == Perms::GROUP_WRITE {
-            repr[4] = "w";
+        let read_write = self & parent_default & READ_WRITE;
+
+        if is_dir {
+            read_write | parent_default & (EXEC | Perms::SET_GID)
+        } else {
+            read_write
         }
+    }
+
+    /// Computes the permissions of a file created with the `requested` mode
+    /// by a process with the given `umask`, as by `open(2)` or `mkdir(2)`.
+    ///
+    /// The following rule is applied:
+    ///
+    /// * Read, write and execute bits are granted where `requested` grants
+    ///   them and `umask` does not, i.e. `requested & !umask & 0o777`.
+    /// * `SET_UID`, `SET_GID` and `STICKY_BIT` are taken from `requested`
+    ///   unchanged. The umask never masks the special bits, so any special
+    ///   bits set in `umask` are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::Perms;
+    ///
+    /// let umask = Perms::try_from(0o022).unwrap();
+    ///
+    /// let file = Perms::effective_create_mode(Perms::try_from(0o666).unwrap(), umask);
+    /// assert_eq!(u32::from(file), 0o644);
+    ///
+    /// let dir = Perms::effective_create_mode(Perms::try_from(0o1777).unwrap(), umask);
+    /// assert_eq!(u32::from(dir), 0o1755);
+    /// ```
+    pub fn effective_create_mode(requested: Perms, umask: Perms) -> Perms {
+        const SPECIAL: u32 = 0o7000;
+
+        let Perms(requested) = requested;
+        let Perms(umask) = umask;
+
+        Perms(requested & SPECIAL | requested & !umask & Perms::ALL.0)
+    }
+
+    /// Returns `true` if every permission bit set in `self` is also set in
+    /// `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_fs_perms::Perms;
+    ///
+    /// assert!(Perms::OWNER_READ.is_subset_of(Perms::ALL));
+    /// assert!(!Perms::SET_UID.is_subset_of(Perms::ALL));
+    /// ```
+    pub fn is_subset_of(self, other: Perms) -> bool {
+        self & other == self
+    }
 
-        match (
-            self & Perms::GROUP_EXEC == Perms::GROUP_EXEC,
-            self & Perms::SET_GID == Perms::SET_GID,
-        ) {
-            (true, false) => repr[5] = "x",
-            (true, true) => repr[5] = "s",
-            (false, true) => repr[5] = "S",
-            (false, false) => (),
+    /// Returns `self` if it only contains permission bits that are present in
+    /// `allowed`.
+    ///
+    /// This is a generalization of the `MASK` check performed by `TryFrom`
+    /// to arbitrary policy masks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::Perms;
+    ///
+    /// let no_write = Perms::try_from(0o555).unwrap();
+    ///
+    /// assert!(Perms::try_from(0o555).unwrap().validate_within(no_write).is_ok());
+    /// assert!(Perms::try_from(0o755).unwrap().validate_within(no_write).is_err());
+    /// ```
+    pub fn validate_within(self, allowed: Perms) -> Result<Perms, PermsTryFromError> {
+        if self.is_subset_of(allowed) {
+            Ok(self)
+        } else {
+            Err(PermsTryFromError(()))
         }
+    }
 
-        if self & Perms::OTHERS_READ == Perms::OTHERS_READ {
-            repr[6] = "r";
+    /// Writes a human readable description of the permissions into `w`.
+    ///
+    /// This is the allocation free core of [`describe`], and is available
+    /// without the `std` feature.
+    ///
+    /// [`describe`]: #method.describe
+    pub fn write_description<W: fmt::Write>(self, w: &mut W) -> fmt::Result {
+        const CLASS_NAMES: [(Class, &str); 3] = [
+            (Class::Owner, "owner"),
+            (Class::Group, "group"),
+            (Class::Others, "others"),
+        ];
+        const OP_NAMES: [(Op, &str); 3] = [
+            (Op::Read, "read"),
+            (Op::Write, "write"),
+            (Op::Exec, "execute"),
+        ];
+        const SPECIAL: [(Perms, &str); 3] = [
+            (Perms::SET_UID, "setuid"),
+            (Perms::SET_GID, "setgid"),
+            (Perms::STICKY_BIT, "sticky"),
+        ];
+
+        for (i, &(class, class_name)) in CLASS_NAMES.iter().enumerate() {
+            if i > 0 {
+                w.write_str("; ")?;
+            }
+
+            write!(w, "{}: ", class_name)?;
+
+            let mut ops = OP_NAMES.iter().filter(|&&(op, _)| self.allows(class, op));
+
+            match ops.next() {
+                Some(&(_, op_name)) => w.write_str(op_name)?,
+                None => w.write_str("none")?,
+            }
+
+            for &(_, op_name) in ops {
+                write!(w, ", {}", op_name)?;
+            }
         }
 
-        if self & Perms::OTHERS_WRITE == Perms::OTHERS_WRITE {
-            repr[7] = "w";
+        let mut special = SPECIAL.iter().filter(|&&(bit, _)| self & bit == bit);
+
+        if let Some(&(_, name)) = special.next() {
+            write!(w, "; special: {}", name)?;
+
+            for &(_, name) in special {
+                write!(w, ", {}", name)?;
+            }
         }
 
-        match (
-            self & Perms::OTHERS_EXEC == Perms::OTHERS_EXEC,
-            self & Perms::STICKY_BIT == Perms::STICKY_BIT,
-        ) {
-            (true, false) => repr[8] = "x",
-            (true, true) => repr[8] = "t",
-            (false, true) => repr[8] = "T",
-            (false, false) => (),
+        w.write_str(".")
+    }
+
+    /// Returns a human readable description of the permissions.
+    ///
+    /// Unlike the terse symbolic `Display` implementation this is intended
+    /// for end-user facing messages.
+    ///
+    /// **Note**: Requires the feature `std`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::Perms;
+    ///
+    /// assert_eq!(
+    ///     Perms::try_from(0o644).unwrap().describe(),
+    ///     "owner: read, write; group: read; others: read.",
+    /// );
+    ///
+    /// assert_eq!(
+    ///     Perms::try_from(0o4750).unwrap().describe(),
+    ///     "owner: read, write, execute; group: read, execute; others: none; special: setuid.",
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn describe(self) -> ::std::string::String {
+        let mut s = ::std::string::String::new();
+        let _ = self.write_description(&mut s);
+
+        s
+    }
+
+    /// Returns the shortest symbolic `chmod` mode which transforms `self`
+    /// into `target`, e.g. `"g-w,o+x"`.
+    ///
+    /// Each class is given a `+`/`-` clause, or an `=` clause when that is
+    /// shorter. Classes which require identical clauses are combined, using
+    /// `a` when all three classes are combined. If `self` is equal to
+    /// `target` then an empty string is returned.
+    ///
+    /// **Note**: Requires the feature `std`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::Perms;
+    ///
+    /// let current = Perms::try_from(0o664).unwrap();
+    ///
+    /// assert_eq!(current.symbolic_from(Perms::try_from(0o645).unwrap()), "g-w,o+x");
+    /// assert_eq!(current.symbolic_from(Perms::try_from(0o775).unwrap()), "a+x");
+    /// assert_eq!(current.symbolic_from(Perms::try_from(0o4700).unwrap()), "u+xs,go=");
+    /// assert_eq!(current.symbolic_from(current), "");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn symbolic_from(self, target: Perms) -> ::std::string::String {
+        use ::std::string::String;
+
+        const CLASS_CHARS: [(char, Class, Perms, char); 3] = [
+            ('u', Class::Owner, Perms::SET_UID, 's'),
+            ('g', Class::Group, Perms::SET_GID, 's'),
+            ('o', Class::Others, Perms::STICKY_BIT, 't'),
+        ];
+        const OP_CHARS: [(Op, char); 3] = [(Op::Read, 'r'), (Op::Write, 'w'), (Op::Exec, 'x')];
+
+        let mut clauses: [(String, String); 3] = Default::default();
+
+        for (&(who, class, special, special_char), clause) in CLASS_CHARS.iter().zip(&mut clauses) {
+            let mut add = String::new();
+            let mut remove = String::new();
+            let mut set = String::new();
+
+            let bits = OP_CHARS
+                .iter()
+                .map(|&(op, c)| (Perms::bit(class, op), c))
+                .chain(Some((special, special_char)));
+
+            for (bit, c) in bits {
+                match (self & bit == bit, target & bit == bit) {
+                    (false, true) => add.push(c),
+                    (true, false) => remove.push(c),
+                    _ => (),
+                }
+
+                if target & bit == bit {
+                    set.push(c);
+                }
+            }
+
+            if add.is_empty() && remove.is_empty() {
+                continue;
+            }
+
+            let mut op = String::new();
+
+            if !add.is_empty() {
+                op.push('+');
+                op.push_str(&add);
+            }
+
+            if !remove.is_empty() {
+                op.push('-');
+                op.push_str(&remove);
+            }
+
+            if set.len() + 1 < op.len() {
+                op = String::from("=");
+                op.push_str(&set);
+            }
+
+            clause.0.push(who);
+            clause.1 = op;
         }
 
-        for bit in repr.into_iter() {
-            f.write_str(bit)?;
+        let mut spec = String::new();
+
+        for i in 0..clauses.len() {
+            if clauses[i].0.is_empty() {
+                continue;
+            }
+
+            for j in i + 1..clauses.len() {
+                if !clauses[j].0.is_empty() && clauses[j].1 == clauses[i].1 {
+                    let who = clauses[j].0.split_off(0);
+                    clauses[i].0.push_str(&who);
+                }
+            }
+
+            if !spec.is_empty() {
+                spec.push(',');
+            }
+
+            if clauses[i].0.len() == 3 {
+                spec.push('a');
+            } else {
+                spec.push_str(&clauses[i].0);
+            }
+
+            spec.push_str(&clauses[i].1);
         }
 
-        Ok(())
+        spec
+    }
+
+    /// Returns an iterator over the access permission changes required to go
+    /// from `self` to `target`.
+    ///
+    /// One [`PermChange`] is yielded per toggled bit, ordered by class and
+    /// then by operation. Changes to the special bits (`SET_UID`, `SET_GID`
+    /// and `STICKY_BIT`) are not reported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::{Class, Op, PermChange, Perms};
+    ///
+    /// let from = Perms::try_from(0o644).unwrap();
+    /// let to = Perms::try_from(0o640).unwrap() | Perms::OWNER_EXEC;
+    ///
+    /// let events: Vec<_> = from.change_events(to).collect();
+    ///
+    /// assert_eq!(
+    ///     events,
+    ///     [
+    ///         PermChange { class: Class::Owner, op: Op::Exec, granted: true },
+    ///         PermChange { class: Class::Others, op: Op::Read, granted: false },
+    ///     ],
+    /// );
+    /// ```
+    ///
+    /// [`PermChange`]: struct.PermChange.html
+    pub fn change_events(self, target: Perms) -> impl Iterator<Item = PermChange> {
+        CLASSES
+            .iter()
+            .flat_map(|&class| OPS.iter().map(move |&op| (class, op)))
+            .filter_map(move |(class, op)| {
+                let granted = target.allows(class, op);
+
+                if self.allows(class, op) == granted {
+                    None
+                } else {
+                    Some(PermChange { class, op, granted })
+                }
+            })
+    }
+
+    /// Returns `self` with execute permissions removed, for use on files that
+    /// must never be executed, such as user uploads.
+    ///
+    /// This clears `OWNER_EXEC`, `GROUP_EXEC` and `OTHERS_EXEC`, as well as
+    /// `SET_UID` and `SET_GID`, which only grant privileges when a file is
+    /// executed. `STICKY_BIT` is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::Perms;
+    ///
+    /// let perms = Perms::try_from(0o6755).unwrap().without_exec();
+    ///
+    /// assert_eq!(u32::from(perms), 0o644);
+    /// ```
+    pub fn without_exec(self) -> Perms {
+        const EXEC: Perms = Perms(0o6111);
+
+        Self(self.0 & !EXEC.0)
+    }
+
+    /// Returns `self` with `SET_UID` and `SET_GID` cleared if the file is
+    /// writable by anyone other than its owner.
+    ///
+    /// The set-id bits are cleared exactly when `GROUP_WRITE` or
+    /// `OTHERS_WRITE` is set. `OWNER_WRITE` alone does not clear them, and
+    /// `STICKY_BIT` is always left unchanged.
+    ///
+    /// This mirrors the rule that privileged executables must never be
+    /// modifiable by other users.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::Perms;
+    ///
+    /// let perms = Perms::try_from(0o6775).unwrap().clear_setid_if_writable();
+    /// assert_eq!(u32::from(perms), 0o775);
+    ///
+    /// let perms = Perms::try_from(0o4755).unwrap().clear_setid_if_writable();
+    /// assert_eq!(u32::from(perms), 0o4755);
+    /// ```
+    pub fn clear_setid_if_writable(self) -> Perms {
+        const SET_ID: Perms = Perms(0o6000);
+
+        if self.is_group_writable() || self.is_world_writable() {
+            Self(self.0 & !SET_ID.0)
+        } else {
+            self
+        }
+    }
+
+    /// Returns `true` if `SET_UID` is set without `OWNER_EXEC`, or `SET_GID`
+    /// is set without `GROUP_EXEC`.
+    ///
+    /// Such bits are displayed as `S` and grant no privileges on execution.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::Perms;
+    ///
+    /// assert!(Perms::try_from(0o4644).unwrap().has_redundant_setid());
+    /// assert!(!Perms::try_from(0o4755).unwrap().has_redundant_setid());
+    /// ```
+    pub fn has_redundant_setid(self) -> bool {
+        self.normalize() != self
+    }
+
+    /// Returns `self` with any redundant set-id bits cleared, so that
+    /// equivalent modes compare equal.
+    ///
+    /// `SET_UID` is cleared if `OWNER_EXEC` is not set, and `SET_GID` is
+    /// cleared if `GROUP_EXEC` is not set. All other bits, including
+    /// `STICKY_BIT`, are left unchanged.
+    ///
+    /// This only changes the meaning of bits which grant no privileges on
+    /// execution. Be aware that some systems give `SET_GID` without
+    /// `GROUP_EXEC` other meanings, such as inheriting the group of a
+    /// directory or mandatory file locking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::Perms;
+    ///
+    /// let perms = Perms::try_from(0o6654).unwrap();
+    ///
+    /// assert_eq!(u32::from(perms.normalize()), 0o2654);
+    /// ```
+    pub fn normalize(self) -> Perms {
+        let mut mode = self.0;
+
+        if !self.allows(Class::Owner, Op::Exec) {
+            mode &= !Perms::SET_UID.0;
+        }
+
+        if !self.allows(Class::Group, Op::Exec) {
+            mode &= !Perms::SET_GID.0;
+        }
+
+        Perms(mode)
+    }
+
+    /// Extracts file access permissions from the external attributes field of
+    /// a zip archive entry.
+    ///
+    /// Zip archives created on Unix store the file mode in the high 16 bits of
+    /// the external attributes. Any file type bits are discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_fs_perms::Perms;
+    ///
+    /// // A regular file (`S_IFREG`) with mode `0644`.
+    /// let perms = Perms::from_zip_external_attrs(0o100644 << 16);
+    ///
+    /// assert_eq!(u32::from(perms), 0o644);
+    /// ```
+    pub fn from_zip_external_attrs(attrs: u32) -> Perms {
+        Self((attrs >> 16) & Self::MASK.0)
+    }
+
+    /// Returns the permissions positioned for the external attributes field
+    /// of a zip archive entry.
+    ///
+    /// Only the permission bits are set. Writers should combine the result
+    /// with the file type bits (e.g. `0o100000 << 16` for a regular file) and
+    /// the MS-DOS attributes in the low byte as required.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::Perms;
+    ///
+    /// let perms = Perms::try_from(0o755).unwrap();
+    ///
+    /// assert_eq!(perms.to_zip_external_attrs(), 0o755 << 16);
+    /// ```
+    pub fn to_zip_external_attrs(self) -> u32 {
+        self.0 << 16
+    }
+
+    /// Attempts to parse file access permissions from the mode field of a tar
+    /// header.
+    ///
+    /// The field is a string of octal digits, which may be padded with
+    /// leading or trailing spaces and NUL bytes. Any file type bits are
+    /// discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_fs_perms::Perms;
+    ///
+    /// let perms = Perms::from_tar_mode(b"0000644\0").unwrap();
+    /// assert_eq!(u32::from(perms), 0o644);
+    ///
+    /// assert!(Perms::from_tar_mode(b"00009\0").is_err());
+    /// ```
+    pub fn from_tar_mode(field: &[u8]) -> Result<Perms, PermsParseError> {
+        let is_padding = |c: &u8| *c == b' ' || *c == 0;
+
+        let start = field
+            .iter()
+            .position(|c| !is_padding(c))
+            .unwrap_or(field.len());
+        let end = field
+            .iter()
+            .rposition(|c| !is_padding(c))
+            .map_or(start, |i| i + 1);
+        let digits = &field[start..end];
+
+        if digits.is_empty() {
+            return Err(PermsParseError(()));
+        }
+
+        digits
+            .iter()
+            .try_fold(0u32, |mode, &c| match c {
+                b'0'..=b'7' => mode.checked_mul(8).map(|mode| mode | u32::from(c - b'0')),
+                _ => None,
+            })
+            .map(|mode| Self(mode & Self::MASK.0))
+            .ok_or(PermsParseError(()))
+    }
+
+    /// Returns the permissions formatted as the mode field of a tar header.
+    ///
+    /// The field is seven zero padded octal digits followed by a NUL byte.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::Perms;
+    ///
+    /// let perms = Perms::try_from(0o4755).unwrap();
+    ///
+    /// assert_eq!(&perms.to_tar_mode(), b"0004755\0");
+    /// ```
+    pub fn to_tar_mode(self) -> [u8; 8] {
+        let mut field = [b'0'; 8];
+        field[7] = 0;
+
+        let mut mode = self.0;
+
+        for c in field[..7].iter_mut().rev() {
+            *c = b'0' + (mode & 0o7) as u8;
+            mode >>= 3;
+        }
+
+        field
+    }
+
+    /// Returns `true` if users outside of the owner and group may write to
+    /// the file, i.e. `OTHERS_WRITE` is set.
+    pub fn is_world_writable(self) -> bool {
+        self.allows(Class::Others, Op::Write)
+    }
+
+    /// Returns `true` if the file's user group may write to the file, i.e.
+    /// `GROUP_WRITE` is set.
+    pub fn is_group_writable(self) -> bool {
+        self.allows(Class::Group, Op::Write)
+    }
+
+    /// Returns `true` if users outside of the owner and group may read the
+    /// file, i.e. `OTHERS_READ` is set.
+    pub fn is_world_readable(self) -> bool {
+        self.allows(Class::Others, Op::Read)
+    }
+
+    /// Summarizes the conditions commonly reported by security audits.
+    ///
+    /// See [`SecurityFlags`] for the conditions that are checked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::{Perms, SecurityFlags};
+    ///
+    /// let flags = Perms::try_from(0o4777).unwrap().security_flags();
+    ///
+    /// assert!(flags.contains(SecurityFlags::WORLD_WRITABLE | SecurityFlags::SET_UID));
+    /// assert!(flags.contains(SecurityFlags::STICKY_MISSING));
+    /// assert!(!flags.contains(SecurityFlags::SET_GID));
+    ///
+    /// assert!(Perms::try_from(0o644).unwrap().security_flags().is_empty());
+    /// ```
+    ///
+    /// [`SecurityFlags`]: struct.SecurityFlags.html
+    pub fn security_flags(self) -> SecurityFlags {
+        let mut flags = SecurityFlags::NONE;
+
+        if self.is_world_writable() {
+            flags = flags | SecurityFlags::WORLD_WRITABLE;
+
+            if !Perms::STICKY_BIT.is_subset_of(self) {
+                flags = flags | SecurityFlags::STICKY_MISSING;
+            }
+        }
+
+        if Perms::SET_UID.is_subset_of(self) {
+            flags = flags | SecurityFlags::SET_UID;
+        }
+
+        if Perms::SET_GID.is_subset_of(self) {
+            flags = flags | SecurityFlags::SET_GID;
+        }
+
+        flags
+    }
+
+    /// Sets the permissions of the file at `path`.
+    ///
+    /// **Note**: Requires the feature `fs`, and is only available on Unix
+    /// platforms.
+    ///
+    /// This is a wrapper around [`std::fs::set_permissions`].
+    ///
+    /// [`std::fs::set_permissions`]: https://doc.rust-lang.org/std/fs/fn.set_permissions.html
+    #[cfg(all(feature = "fs", unix))]
+    pub fn apply_to_path(self, path: &::std::path::Path) -> ::std::io::Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        ::std::fs::set_permissions(path, ::std::fs::Permissions::from_mode(self.0))
+    }
+
+    /// Sets the permissions of each file in `paths`, in order.
+    ///
+    /// **Note**: Requires the feature `fs`, and is only available on Unix
+    /// platforms.
+    ///
+    /// Stops at the first failure, returning the path that could not be
+    /// updated along with the error. Files earlier in `paths` keep their new
+    /// permissions.
+    #[cfg(all(feature = "fs", unix))]
+    pub fn apply_to_paths<'a>(
+        self,
+        paths: &[&'a ::std::path::Path],
+    ) -> Result<(), (&'a ::std::path::Path, ::std::io::Error)> {
+        for &path in paths {
+            self.apply_to_path(path).map_err(|err| (path, err))?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the permissions as two little endian bytes.
+    ///
+    /// This representation is independent of the width of `mode_t` on any
+    /// particular platform, making it suitable for use in wire protocols.
+    ///
+    /// Only the bits within [`MASK`] are represented. Any further bits set
+    /// through [`with_mask`] are dropped, so that the bytes can always be
+    /// read back by [`from_canonical_bytes`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::Perms;
+    ///
+    /// let perms = Perms::try_from(0o4755).unwrap();
+    ///
+    /// assert_eq!(perms.to_canonical_bytes(), [0xED, 0x09]);
+    /// ```
+    ///
+    /// [`MASK`]: #associatedconstant.MASK
+    /// [`with_mask`]: #method.with_mask
+    /// [`from_canonical_bytes`]: #method.from_canonical_bytes
+    pub fn to_canonical_bytes(self) -> [u8; 2] {
+        ((self.0 & Perms::MASK.0) as u16).to_le_bytes()
+    }
+
+    /// Attempts to read permissions from two little endian bytes, as
+    /// produced by [`to_canonical_bytes`].
+    ///
+    /// This fails if any bit above the 12 permission bits is set.
+    ///
+    /// [`to_canonical_bytes`]: #method.to_canonical_bytes
+    pub fn from_canonical_bytes(bytes: [u8; 2]) -> Result<Perms, PermsTryFromError> {
+        Self::try_from(u32::from(u16::from_le_bytes(bytes)))
+    }
+
+    /// Returns the four octal digits of the permissions: the special bits,
+    /// followed by the owner, group and others digits.
+    ///
+    /// Any bits above [`MASK`], set through [`with_mask`], are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::Perms;
+    ///
+    /// let perms = Perms::try_from(0o4755).unwrap();
+    ///
+    /// assert_eq!(perms.octal_digits(), [4, 7, 5, 5]);
+    /// ```
+    ///
+    /// [`MASK`]: #associatedconstant.MASK
+    /// [`with_mask`]: #method.with_mask
+    pub fn octal_digits(self) -> [u8; 4] {
+        let Self(mode) = self;
+
+        [
+            (mode >> 9 & 0o7) as u8,
+            (mode >> 6 & 0o7) as u8,
+            (mode >> 3 & 0o7) as u8,
+            (mode & 0o7) as u8,
+        ]
+    }
+
+    /// Attempts to build permissions from four octal digits, as produced by
+    /// [`octal_digits`].
+    ///
+    /// This fails if any digit is greater than `7`.
+    ///
+    /// [`octal_digits`]: #method.octal_digits
+    pub fn from_octal_digits(digits: [u8; 4]) -> Result<Perms, PermsTryFromError> {
+        if digits.iter().any(|&digit| digit > 0o7) {
+            return Err(PermsTryFromError(()));
+        }
+
+        let mode = digits
+            .iter()
+            .fold(0, |mode, &digit| mode << 3 | u32::from(digit));
+
+        Ok(Perms(mode))
+    }
+
+    /// Returns a score summarizing how permissive the permissions are, for
+    /// use when triaging many files.
+    ///
+    /// The score is the sum of the following weights for each bit that is
+    /// set, so that higher scores are riskier:
+    ///
+    /// | Bit            | Weight |
+    /// | -------------- | ------ |
+    /// | `SET_UID`      | 16     |
+    /// | `SET_GID`      | 12     |
+    /// | `OTHERS_WRITE` | 8      |
+    /// | `GROUP_WRITE`  | 4      |
+    /// | `OTHERS_EXEC`  | 2      |
+    /// | `GROUP_EXEC`   | 1      |
+    /// | `OTHERS_READ`  | 1      |
+    ///
+    /// All other bits have a weight of zero. The score ranges from `0` to
+    /// `44`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::Perms;
+    ///
+    /// assert_eq!(Perms::try_from(0o600).unwrap().openness(), 0);
+    /// assert_eq!(Perms::try_from(0o644).unwrap().openness(), 1);
+    /// assert_eq!(Perms::try_from(0o4755).unwrap().openness(), 20);
+    /// assert_eq!(Perms::MASK.openness(), 44);
+    /// ```
+    pub fn openness(self) -> u8 {
+        const WEIGHTS: [(Perms, u8); 7] = [
+            (Perms::SET_UID, 16),
+            (Perms::SET_GID, 12),
+            (Perms::OTHERS_WRITE, 8),
+            (Perms::GROUP_WRITE, 4),
+            (Perms::OTHERS_EXEC, 2),
+            (Perms::GROUP_EXEC, 1),
+            (Perms::OTHERS_READ, 1),
+        ];
+
+        WEIGHTS
+            .iter()
+            .filter(|&&(bit, _)| bit.is_subset_of(self))
+            .map(|&(_, weight)| weight)
+            .sum()
+    }
+
+    /// Returns the nine cells of the symbolic representation, e.g.
+    /// `"rwsr-xr-T"`, as structured data.
+    ///
+    /// Each cell corresponds to one character of the `Display`
+    /// representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::{Perms, SymCell};
+    ///
+    /// let cells = Perms::try_from(0o4751).unwrap().symbolic_cells();
+    ///
+    /// assert_eq!(cells[..3], [SymCell::Read, SymCell::Write, SymCell::SetidExec]);
+    /// assert_eq!(cells[8], SymCell::Exec);
+    /// ```
+    pub fn symbolic_cells(self) -> [SymCell; 9] {
+        const SPECIALS: [(Class, Perms, SymCell, SymCell); 3] = [
+            (
+                Class::Owner,
+                Perms::SET_UID,
+                SymCell::SetidExec,
+                SymCell::SetidNoExec,
+            ),
+            (
+                Class::Group,
+                Perms::SET_GID,
+                SymCell::SetidExec,
+                SymCell::SetidNoExec,
+            ),
+            (
+                Class::Others,
+                Perms::STICKY_BIT,
+                SymCell::StickyExec,
+                SymCell::StickyNoExec,
+            ),
+        ];
+
+        let mut cells = [SymCell::Unset; 9];
+
+        for (&(class, special, with_exec, without_exec), cells) in
+            SPECIALS.iter().zip(cells.chunks_mut(3))
+        {
+            if self.allows(class, Op::Read) {
+                cells[0] = SymCell::Read;
+            }
+
+            if self.allows(class, Op::Write) {
+                cells[1] = SymCell::Write;
+            }
+
+            match (self.allows(class, Op::Exec), special.is_subset_of(self)) {
+                (true, false) => cells[2] = SymCell::Exec,
+                (true, true) => cells[2] = with_exec,
+                (false, true) => cells[2] = without_exec,
+                (false, false) => (),
+            }
+        }
+
+        cells
+    }
+
+    /// Returns the access permissions as a 3x3 matrix.
+    ///
+    /// Rows are ordered owner, group, others, and columns are ordered read,
+    /// write, execute, so that `matrix[1][2]` is `GROUP_EXEC`. The special
+    /// bits are not represented.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::Perms;
+    ///
+    /// let perms = Perms::try_from(0o751).unwrap();
+    ///
+    /// assert_eq!(
+    ///     perms.to_matrix(),
+    ///     [
+    ///         [true, true, true],
+    ///         [true, false, true],
+    ///         [false, false, true],
+    ///     ],
+    /// );
+    /// ```
+    pub fn to_matrix(self) -> [[bool; 3]; 3] {
+        let mut matrix = [[false; 3]; 3];
+
+        for (row, &class) in matrix.iter_mut().zip(CLASSES.iter()) {
+            for (cell, &op) in row.iter_mut().zip(OPS.iter()) {
+                *cell = self.allows(class, op);
+            }
+        }
+
+        matrix
+    }
+
+    /// Builds access permissions from a 3x3 matrix, as returned by
+    /// [`to_matrix`].
+    ///
+    /// No special bits are set.
+    ///
+    /// [`to_matrix`]: #method.to_matrix
+    pub fn from_matrix(matrix: [[bool; 3]; 3]) -> Perms {
+        let mut perms = Perms::NONE;
+
+        for (row, &class) in matrix.iter().zip(CLASSES.iter()) {
+            for (&cell, &op) in row.iter().zip(OPS.iter()) {
+                if cell {
+                    perms |= Perms::bit(class, op);
+                }
+            }
+        }
+
+        perms
+    }
+
+    /// Returns the union of all of the given permissions.
+    ///
+    /// This is the bitwise or of every item, or [`NONE`] if there are none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::Perms;
+    ///
+    /// let modes = [0o640, 0o604, 0o4700];
+    /// let perms = Perms::union_all(modes.iter().map(|&m| Perms::try_from(m).unwrap()));
+    ///
+    /// assert_eq!(u32::from(perms), 0o4744);
+    /// ```
+    ///
+    /// [`NONE`]: #associatedconstant.NONE
+    pub fn union_all<I>(iter: I) -> Perms
+    where
+        I: IntoIterator<Item = Perms>,
+    {
+        iter.into_iter().fold(Perms::NONE, |acc, perms| acc | perms)
+    }
+
+    /// Returns the intersection of all of the given permissions.
+    ///
+    /// This is the bitwise and of every item, starting from [`MASK`], so
+    /// that [`MASK`] is returned if there are none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::Perms;
+    ///
+    /// let modes = [0o640, 0o604, 0o4700];
+    /// let perms = Perms::intersection_all(modes.iter().map(|&m| Perms::try_from(m).unwrap()));
+    ///
+    /// assert_eq!(u32::from(perms), 0o600);
+    /// ```
+    ///
+    /// [`MASK`]: #associatedconstant.MASK
+    pub fn intersection_all<I>(iter: I) -> Perms
+    where
+        I: IntoIterator<Item = Perms>,
+    {
+        iter.into_iter().fold(Perms::MASK, |acc, perms| acc & perms)
+    }
+
+    /// Writes the symbolic representation into `w`, using the given rendering
+    /// `style` for the special bits.
+    ///
+    /// This is the allocation free core of [`to_string_with_style`], and is
+    /// available without the `std` feature.
+    ///
+    /// [`to_string_with_style`]: #method.to_string_with_style
+    pub fn write_symbolic_with_style<W: fmt::Write>(
+        self,
+        w: &mut W,
+        style: SymStyle,
+    ) -> fmt::Result {
+        for &cell in self.symbolic_cells().iter() {
+            w.write_char(style.render(cell))?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the symbolic representation using the given rendering `style`
+    /// for the special bits.
+    ///
+    /// [`SymStyle::Standard`] matches the `Display` implementation. The
+    /// special bits without any execute permission, `0o7000`, are rendered as
+    /// follows:
+    ///
+    /// | Style                   | Output        |
+    /// |-------------------------|---------------|
+    /// | [`SymStyle::Standard`]  | `"--S--S--T"` |
+    /// | [`SymStyle::Uppercase`] | `"--S--S--T"` |
+    /// | [`SymStyle::Hidden`]    | `"---------"` |
+    ///
+    /// **Note**: Requires the feature `std`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::{Perms, SymStyle};
+    ///
+    /// let perms = Perms::try_from(0o7755).unwrap();
+    ///
+    /// assert_eq!(perms.to_string_with_style(SymStyle::Standard), "rwsr-sr-t");
+    /// assert_eq!(perms.to_string_with_style(SymStyle::Uppercase), "rwSr-Sr-T");
+    /// assert_eq!(perms.to_string_with_style(SymStyle::Hidden), "rwxr-xr-x");
+    /// ```
+    ///
+    /// [`SymStyle::Standard`]: enum.SymStyle.html#variant.Standard
+    /// [`SymStyle::Uppercase`]: enum.SymStyle.html#variant.Uppercase
+    /// [`SymStyle::Hidden`]: enum.SymStyle.html#variant.Hidden
+    #[cfg(feature = "std")]
+    pub fn to_string_with_style(self, style: SymStyle) -> ::std::string::String {
+        let mut s = ::std::string::String::new();
+        let _ = self.write_symbolic_with_style(&mut s, style);
+
+        s
+    }
+
+    /// Returns permissions corresponding to a Windows style readonly flag.
+    ///
+    /// This is `0o444` (`"r--r--r--"`) if `readonly` is `true`, and `0o644`
+    /// (`"rw-r--r--"`) otherwise, matching the default modes of regular files
+    /// under a typical umask.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_fs_perms::Perms;
+    ///
+    /// assert_eq!(Perms::from_readonly(true).to_string(), "r--r--r--");
+    /// assert_eq!(Perms::from_readonly(false).to_string(), "rw-r--r--");
+    /// ```
+    pub const fn from_readonly(readonly: bool) -> Self {
+        if readonly {
+            Self(0o444)
+        } else {
+            Self(0o644)
+        }
+    }
+
+    /// Returns `true` if no class of users may write to the file.
+    ///
+    /// This is the inverse of the mapping used by [`from_readonly`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::Perms;
+    ///
+    /// assert!(Perms::try_from(0o555).unwrap().is_effectively_readonly());
+    /// assert!(!Perms::try_from(0o602).unwrap().is_effectively_readonly());
+    /// ```
+    ///
+    /// [`from_readonly`]: #method.from_readonly
+    pub fn is_effectively_readonly(self) -> bool {
+        const WRITE: Perms = Perms(0o222);
+
+        self & WRITE == Perms::NONE
+    }
+
+    /// Returns `true` if the permission bits of a raw mode are equal to
+    /// `self`.
+    ///
+    /// Any bits of `raw` outside of [`MASK`], such as the file type bits of an
+    /// `st_mode`, are ignored. So are any such bits of `self`, set through
+    /// [`with_mask`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use nano_fs_perms::Perms;
+    ///
+    /// let perms = Perms::try_from(0o644).unwrap();
+    ///
+    /// // A regular file (`S_IFREG`) with mode 0644.
+    /// assert!(perms.matches_mode(0o100644));
+    /// assert!(!perms.matches_mode(0o100664));
+    /// ```
+    ///
+    /// [`MASK`]: #associatedconstant.MASK
+    /// [`with_mask`]: #method.with_mask
+    pub fn matches_mode(self, raw: u32) -> bool {
+        raw & Perms::MASK.0 == self.0 & Perms::MASK.0
+    }
+}
+
+/// A builder for file access permissions.
+///
+/// Builders are created by [`Perms::builder`]. Each method grants further
+/// permissions, so that a mode can be spelled out one class at a time.
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use nano_fs_perms::{Class, Op, Perms};
+///
+/// let perms = Perms::builder()
+///     .owner_rwx()
+///     .group_read()
+///     .grant(Class::Group, Op::Exec)
+///     .sticky()
+///     .build();
+///
+/// assert_eq!(perms, Perms::try_from(0o1750).unwrap());
+/// ```
+///
+/// [`Perms::builder`]: struct.Perms.html#method.builder
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct PermsBuilder(Perms);
+
+impl PermsBuilder {
+    /// Grants read permission to the owner.
+    pub const fn owner_read(self) -> Self {
+        self.with(Perms::OWNER_READ)
+    }
+
+    /// Grants write permission to the owner.
+    pub const fn owner_write(self) -> Self {
+        self.with(Perms::OWNER_WRITE)
+    }
+
+    /// Grants execute permission to the owner.
+    pub const fn owner_exec(self) -> Self {
+        self.with(Perms::OWNER_EXEC)
+    }
+
+    /// Grants read, write and execute permissions to the owner.
+    pub const fn owner_rwx(self) -> Self {
+        self.with(Perms::OWNER_ALL)
+    }
+
+    /// Grants read permission to the group.
+    pub const fn group_read(self) -> Self {
+        self.with(Perms::GROUP_READ)
+    }
+
+    /// Grants write permission to the group.
+    pub const fn group_write(self) -> Self {
+        self.with(Perms::GROUP_WRITE)
+    }
+
+    /// Grants execute permission to the group.
+    pub const fn group_exec(self) -> Self {
+        self.with(Perms::GROUP_EXEC)
+    }
+
+    /// Grants read, write and execute permissions to the group.
+    pub const fn group_rwx(self) -> Self {
+        self.with(Perms::GROUP_ALL)
+    }
+
+    /// Grants read permission to the other users.
+    pub const fn others_read(self) -> Self {
+        self.with(Perms::OTHERS_READ)
+    }
+
+    /// Grants write permission to the other users.
+    pub const fn others_write(self) -> Self {
+        self.with(Perms::OTHERS_WRITE)
+    }
+
+    /// Grants execute permission to the other users.
+    pub const fn others_exec(self) -> Self {
+        self.with(Perms::OTHERS_EXEC)
+    }
+
+    /// Grants read, write and execute permissions to the other users.
+    pub const fn others_rwx(self) -> Self {
+        self.with(Perms::OTHERS_ALL)
+    }
+
+    /// Sets the `SET_UID` bit.
+    pub const fn setuid(self) -> Self {
+        self.with(Perms::SET_UID)
+    }
+
+    /// Sets the `SET_GID` bit.
+    pub const fn setgid(self) -> Self {
+        self.with(Perms::SET_GID)
+    }
+
+    /// Sets the `STICKY_BIT`.
+    pub const fn sticky(self) -> Self {
+        self.with(Perms::STICKY_BIT)
+    }
+
+    /// Grants `op` to `class`.
+    pub fn grant(self, class: Class, op: Op) -> Self {
+        self.with(Perms::bit(class, op))
+    }
+
+    /// Returns the built permissions.
+    pub const fn build(self) -> Perms {
+        let Self(perms) = self;
+
+        perms
+    }
+
+    const fn with(self, bits: Perms) -> Self {
+        let Self(Perms(mode)) = self;
+
+        Self(Perms(mode | bits.0))
+    }
+}
+
+/// Every class, in the order in which they appear in the symbolic
+/// representation.
+const CLASSES: [Class; 3] = [Class::Owner, Class::Group, Class::Others];
+
+/// Every operation, in the order in which they appear in the symbolic
+/// representation.
+const OPS: [Op; 3] = [Op::Read, Op::Write, Op::Exec];
+
+/// A class of users that file access permissions apply to.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Class {
+    /// The file owner.
+    Owner,
+    /// The file's user group.
+    Group,
+    /// All other users.
+    Others,
+}
+
+/// An operation that file access permissions may permit.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Op {
+    /// Reading the file.
+    Read,
+    /// Writing to the file.
+    Write,
+    /// Executing the file, or searching the directory.
+    Exec,
+}
+
+/// A single cell of the symbolic representation of file access permissions.
+///
+/// Cells are produced by [`Perms::symbolic_cells`].
+///
+/// [`Perms::symbolic_cells`]: struct.Perms.html#method.symbolic_cells
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SymCell {
+    /// Read permission (`r`).
+    Read,
+    /// Write permission (`w`).
+    Write,
+    /// Execute permission (`x`).
+    Exec,
+    /// Execute permission with `SET_UID` or `SET_GID` (`s`).
+    SetidExec,
+    /// `SET_UID` or `SET_GID` without execute permission (`S`).
+    SetidNoExec,
+    /// Execute permission with `STICKY_BIT` (`t`).
+    StickyExec,
+    /// `STICKY_BIT` without execute permission (`T`).
+    StickyNoExec,
+    /// No permission (`-`).
+    Unset,
+}
+
+impl SymCell {
+    /// Returns the character used for this cell in the symbolic
+    /// representation.
+    pub fn as_char(self) -> char {
+        match self {
+            SymCell::Read => 'r',
+            SymCell::Write => 'w',
+            SymCell::Exec => 'x',
+            SymCell::SetidExec => 's',
+            SymCell::SetidNoExec => 'S',
+            SymCell::StickyExec => 't',
+            SymCell::StickyNoExec => 'T',
+            SymCell::Unset => '-',
+        }
+    }
+}
+
+/// A rendering style for the special bits in the symbolic representation.
+///
+/// Styles are used by [`Perms::to_string_with_style`].
+///
+/// [`Perms::to_string_with_style`]: struct.Perms.html#method.to_string_with_style
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum SymStyle {
+    /// Special bits are shown as `s`/`t` with execute permission and as
+    /// `S`/`T` without, as by `ls -l` and the `Display` implementation.
+    #[default]
+    Standard,
+    /// Special bits are always shown as `S`/`T`, regardless of execute
+    /// permission.
+    Uppercase,
+    /// Special bits are not shown; the cell shows only execute permission.
+    Hidden,
+}
+
+impl SymStyle {
+    fn render(self, cell: SymCell) -> char {
+        match (self, cell) {
+            (SymStyle::Uppercase, SymCell::SetidExec) => 'S',
+            (SymStyle::Uppercase, SymCell::StickyExec) => 'T',
+            (SymStyle::Hidden, SymCell::SetidExec) | (SymStyle::Hidden, SymCell::StickyExec) => 'x',
+            (SymStyle::Hidden, SymCell::SetidNoExec)
+            | (SymStyle::Hidden, SymCell::StickyNoExec) => '-',
+            (_, cell) => cell.as_char(),
+        }
+    }
+}
+
+/// A set of risky conditions reported by [`Perms::security_flags`].
+///
+/// [`Perms::security_flags`]: struct.Perms.html#method.security_flags
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct SecurityFlags(u8);
+
+impl SecurityFlags {
+    /// No risky conditions.
+    pub const NONE: Self = Self(0);
+
+    /// Users outside of the owner and group may write to the file.
+    pub const WORLD_WRITABLE: Self = Self(1);
+
+    /// The file executes with the privileges of its owner.
+    pub const SET_UID: Self = Self(1 << 1);
+
+    /// The file executes with the privileges of its group.
+    pub const SET_GID: Self = Self(1 << 2);
+
+    /// The file is world writable but the sticky bit is not set.
+    ///
+    /// This is only a concern for directories, where any user may then
+    /// delete or rename files owned by other users.
+    pub const STICKY_MISSING: Self = Self(1 << 3);
+
+    /// Returns `true` if every condition in `other` is also in `self`.
+    pub fn contains(self, other: SecurityFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns `true` if no conditions are set.
+    pub fn is_empty(self) -> bool {
+        self == Self::NONE
+    }
+}
+
+impl ops::BitOr for SecurityFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// A single permission bit being granted or revoked.
+///
+/// Changes are produced by [`Perms::change_events`].
+///
+/// [`Perms::change_events`]: struct.Perms.html#method.change_events
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct PermChange {
+    /// The class of users affected by the change.
+    pub class: Class,
+    /// The operation affected by the change.
+    pub op: Op,
+    /// `true` if the permission is granted, `false` if it is revoked.
+    pub granted: bool,
+}
+
+impl fmt::Display for Perms {
+    /// Formats the symbolic representation, e.g. `"rwsr-xr-T"`.
+    ///
+    /// The width, fill and alignment of the formatter are respected.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut repr = [0; 9];
+
+        for (c, cell) in repr.iter_mut().zip(self.symbolic_cells().iter()) {
+            *c = cell.as_char() as u8;
+        }
+
+        // Every symbolic cell is ASCII.
+        f.pad(str::from_utf8(&repr).map_err(|_| fmt::Error)?)
     }
 }
 
@@ -1,7 +1,9 @@
-use nano_fs_perms::Perms;
+use nano_fs_perms::{Class, Op, PermChange, Perms, PermsBuilder, SecurityFlags, SymCell, SymStyle};
 
 use std::convert::TryFrom;
 
+use quickcheck_macros::quickcheck;
+
 struct TestCase<'a> {
     perms: Perms,
     literal: u32,
@@ -87,3 +89,773 @@ fn perms() {
         assert_eq!(&format!("{}", perms), display, "{}", display);
     }
 }
+
+#[test]
+fn perms_allows() {
+    let classes = [Class::Owner, Class::Group, Class::Others];
+    let ops = [Op::Read, Op::Write, Op::Exec];
+
+    for &class in classes.iter() {
+        for &op in ops.iter() {
+            assert!(Perms::ALL.allows(class, op), "{:?} {:?}", class, op);
+            assert!(!Perms::NONE.allows(class, op), "{:?} {:?}", class, op);
+            assert!(
+                !(Perms::MASK ^ Perms::bit(class, op)).allows(class, op),
+                "{:?} {:?}",
+                class,
+                op
+            );
+        }
+    }
+
+    let perms = Perms::try_from(0o640).expect("perms");
+
+    assert!(perms.allows(Class::Owner, Op::Read));
+    assert!(perms.allows(Class::Owner, Op::Write));
+    assert!(!perms.allows(Class::Owner, Op::Exec));
+    assert!(perms.allows(Class::Group, Op::Read));
+    assert!(!perms.allows(Class::Group, Op::Write));
+    assert!(!perms.allows(Class::Others, Op::Read));
+}
+
+#[test]
+fn perms_parse() {
+    let cases = [
+        ("---------", 0),
+        ("rw-r--r--", 0o644),
+        ("rwxr-xr-x", 0o755),
+        ("rwsrwsrwt", 0o7777),
+        ("--S--S--T", 0o7000),
+        ("rwSr-sr-t", 0o7655),
+    ];
+
+    for &(symbolic, literal) in cases.iter() {
+        let perms = Perms::try_from(literal).expect(symbolic);
+        let octal = format!("{:o}", literal);
+
+        assert_eq!(symbolic.parse::<Perms>(), Ok(perms), "{}", symbolic);
+        assert_eq!(Perms::from_octal_str(&octal), Ok(perms), "{}", octal);
+        assert_eq!(Perms::try_from(symbolic), Ok(perms), "{}", symbolic);
+        assert_eq!(Perms::try_from(&*octal), Ok(perms), "{}", octal);
+    }
+}
+
+#[test]
+fn perms_parse_error() {
+    let cases = [
+        "",
+        "rw-r--r-",
+        "rw-r--r--x",
+        "wr-r--r--",
+        "rwtr--r--",
+        "rw-r--r-s",
+        "0o644",
+        "-644",
+        "+644",
+        "689",
+        "17777",
+    ];
+
+    for &case in cases.iter() {
+        assert!(Perms::try_from(case).is_err(), "{}", case);
+    }
+}
+
+#[test]
+fn perms_inherit_from() {
+    let cases = [
+        (0o666, 0o755, false, 0o644),
+        (0o666, 0o755, true, 0o755),
+        (0o777, 0o750, false, 0o640),
+        (0o755, 0o775, false, 0o644),
+        (0o644, 0o777, false, 0o644),
+        (0o600, 0o777, true, 0o711),
+        (0o7777, 0o7777, false, 0o666),
+        (0o7777, 0o7777, true, 0o2777),
+        (0o777, 0o2000, true, 0o2000),
+    ];
+
+    for &(requested, parent_default, is_dir, expected) in cases.iter() {
+        let requested = Perms::try_from(requested).expect("requested");
+        let parent_default = Perms::try_from(parent_default).expect("parent");
+
+        assert_eq!(
+            u32::from(requested.inherit_from(parent_default, is_dir)),
+            expected,
+            "{} {} {}",
+            requested,
+            parent_default,
+            is_dir
+        );
+    }
+}
+
+#[test]
+fn perms_validate_within() {
+    let allowed = Perms::try_from(0o755).expect("allowed");
+
+    for value in 0..=0o7777 {
+        let perms = Perms::try_from(value).expect("perms");
+        let expected = value & !0o755 == 0;
+
+        assert_eq!(perms.is_subset_of(allowed), expected, "{:o}", value);
+        assert_eq!(
+            perms.validate_within(allowed).is_ok(),
+            expected,
+            "{:o}",
+            value
+        );
+    }
+
+    assert_eq!(Perms::NONE.validate_within(Perms::NONE), Ok(Perms::NONE));
+    assert_eq!(Perms::MASK.validate_within(Perms::MASK), Ok(Perms::MASK));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn perms_describe() {
+    let cases = [
+        (0, "owner: none; group: none; others: none."),
+        (
+            0o777,
+            "owner: read, write, execute; group: read, write, execute; \
+             others: read, write, execute.",
+        ),
+        (0o640, "owner: read, write; group: read; others: none."),
+        (
+            0o1770,
+            "owner: read, write, execute; group: read, write, execute; \
+             others: none; special: sticky.",
+        ),
+        (
+            0o7000,
+            "owner: none; group: none; others: none; special: setuid, setgid, sticky.",
+        ),
+    ];
+
+    for &(literal, description) in cases.iter() {
+        let perms = Perms::try_from(literal).expect(description);
+        assert_eq!(perms.describe(), description);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn perms_symbolic_from() {
+    let cases = [
+        (0o644, 0o644, ""),
+        (0o644, 0o755, "a+x"),
+        (0o644, 0o754, "ug+x"),
+        (0o664, 0o645, "g-w,o+x"),
+        (0o777, 0o700, "go="),
+        (0o000, 0o777, "a+rwx"),
+        (0o777, 0o000, "a="),
+        (0o755, 0o4755, "u+s"),
+        (0o755, 0o6755, "ug+s"),
+        (0o777, 0o1777, "o+t"),
+        (0o1777, 0o777, "o-t"),
+        (0o640, 0o604, "g=,o+r"),
+        (0o4500, 0o4300, "u+w-r"),
+        (0o123, 0o456, "u=r,g=rx,o=rw"),
+    ];
+
+    for &(current, target, spec) in cases.iter() {
+        let current = Perms::try_from(current).expect("current");
+        let target = Perms::try_from(target).expect("target");
+
+        assert_eq!(
+            current.symbolic_from(target),
+            spec,
+            "{} {}",
+            current,
+            target
+        );
+    }
+}
+
+#[test]
+fn perms_change_events() {
+    let from = Perms::try_from(0o4754).unwrap();
+    let to = Perms::try_from(0o0147).unwrap();
+
+    let events: Vec<_> = from.change_events(to).collect();
+    assert_eq!(
+        events,
+        [
+            PermChange {
+                class: Class::Owner,
+                op: Op::Read,
+                granted: false
+            },
+            PermChange {
+                class: Class::Owner,
+                op: Op::Write,
+                granted: false
+            },
+            PermChange {
+                class: Class::Group,
+                op: Op::Exec,
+                granted: false
+            },
+            PermChange {
+                class: Class::Others,
+                op: Op::Write,
+                granted: true
+            },
+            PermChange {
+                class: Class::Others,
+                op: Op::Exec,
+                granted: true
+            },
+        ]
+    );
+
+    assert_eq!(from.change_events(from).count(), 0);
+}
+
+#[test]
+fn perms_without_exec() {
+    assert_eq!(
+        u32::from(Perms::try_from(0o7777).unwrap().without_exec()),
+        0o1666
+    );
+    assert_eq!(
+        u32::from(Perms::try_from(0o640).unwrap().without_exec()),
+        0o640
+    );
+    assert_eq!(Perms::OTHERS_EXEC.without_exec(), Perms::NONE);
+}
+
+#[test]
+fn perms_zip_external_attrs() {
+    for &mode in &[0o0, 0o644, 0o755, 0o7777] {
+        let perms = Perms::try_from(mode).unwrap();
+        let attrs = perms.to_zip_external_attrs();
+
+        assert_eq!(attrs, mode << 16);
+        assert_eq!(
+            Perms::from_zip_external_attrs(attrs | 0o100000 << 16 | 0x20),
+            perms
+        );
+    }
+}
+
+#[test]
+fn perms_tar_mode() {
+    for &mode in &[0o0, 0o644, 0o755, 0o7777] {
+        let perms = Perms::try_from(mode).unwrap();
+        assert_eq!(Perms::from_tar_mode(&perms.to_tar_mode()), Ok(perms));
+    }
+
+    assert_eq!(
+        u32::from(Perms::from_tar_mode(b"   644 \0").unwrap()),
+        0o644
+    );
+    assert_eq!(
+        u32::from(Perms::from_tar_mode(b"0100755\0").unwrap()),
+        0o755
+    );
+
+    assert!(Perms::from_tar_mode(b"\0\0\0\0\0\0\0\0").is_err());
+    assert!(Perms::from_tar_mode(b"06 44\0").is_err());
+    assert!(Perms::from_tar_mode(b"77777777777777").is_err());
+}
+
+#[test]
+fn perms_security_predicates() {
+    let perms = Perms::try_from(0o664).unwrap();
+    assert!(perms.is_group_writable());
+    assert!(perms.is_world_readable());
+    assert!(!perms.is_world_writable());
+
+    let perms = Perms::try_from(0o602).unwrap();
+    assert!(!perms.is_group_writable());
+    assert!(!perms.is_world_readable());
+    assert!(perms.is_world_writable());
+}
+
+#[test]
+fn perms_security_flags() {
+    assert_eq!(
+        Perms::try_from(0o755).unwrap().security_flags(),
+        SecurityFlags::NONE
+    );
+    assert_eq!(
+        Perms::try_from(0o1777).unwrap().security_flags(),
+        SecurityFlags::WORLD_WRITABLE
+    );
+    assert_eq!(
+        Perms::try_from(0o777).unwrap().security_flags(),
+        SecurityFlags::WORLD_WRITABLE | SecurityFlags::STICKY_MISSING
+    );
+    assert_eq!(
+        Perms::try_from(0o6755).unwrap().security_flags(),
+        SecurityFlags::SET_UID | SecurityFlags::SET_GID
+    );
+}
+
+#[cfg(all(feature = "fs", unix))]
+#[test]
+fn perms_apply_to_paths() {
+    use std::os::unix::fs::PermissionsExt;
+    use std::{fs, process};
+
+    let dir = std::env::temp_dir().join(format!("nano-fs-perms-{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let a = dir.join("a");
+    let b = dir.join("b");
+    let missing = dir.join("missing");
+    fs::write(&a, b"").unwrap();
+    fs::write(&b, b"").unwrap();
+
+    let perms = Perms::try_from(0o640).unwrap();
+    perms.apply_to_paths(&[&a, &b]).unwrap();
+
+    for path in &[&a, &b] {
+        let mode = fs::metadata(path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o7777, 0o640);
+    }
+
+    let (path, _) = Perms::try_from(0o600)
+        .unwrap()
+        .apply_to_paths(&[&a, &missing, &b])
+        .unwrap_err();
+    assert_eq!(path, missing.as_path());
+
+    assert_eq!(
+        fs::metadata(&a).unwrap().permissions().mode() & 0o7777,
+        0o600
+    );
+    assert_eq!(
+        fs::metadata(&b).unwrap().permissions().mode() & 0o7777,
+        0o640
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn perms_macro() {
+    use nano_fs_perms::perms;
+
+    const TABLE: [Perms; 4] = [
+        perms!("rw-r--r--"),
+        perms!(0o755),
+        perms!(0x1ff),
+        perms!(0o4_7_5_5u32),
+    ];
+
+    assert_eq!(TABLE[0], Perms::try_from(0o644).unwrap());
+    assert_eq!(TABLE[1], Perms::try_from(0o755).unwrap());
+    assert_eq!(TABLE[2], Perms::ALL);
+    assert_eq!(TABLE[3], Perms::try_from(0o4755).unwrap());
+
+    for &mode in &[0o0, 0o644, 0o1777, 0o6755] {
+        let perms = Perms::try_from(mode).unwrap();
+        assert_eq!(
+            Perms::__from_literal(&format!("{:?}", perms.to_string())),
+            perms
+        );
+        assert_eq!(Perms::__from_literal(&format!("0o{:o}", mode)), perms);
+    }
+}
+
+#[test]
+fn perms_canonical_bytes() {
+    for mode in 0..=0o7777 {
+        let perms = Perms::try_from(mode).unwrap();
+        let bytes = perms.to_canonical_bytes();
+
+        assert_eq!(u32::from(u16::from_le_bytes(bytes)), mode);
+        assert_eq!(Perms::from_canonical_bytes(bytes), Ok(perms));
+    }
+
+    assert!(Perms::from_canonical_bytes([0x00, 0x10]).is_err());
+}
+
+#[quickcheck]
+fn qc_perms_conversions(digits: (u8, u8, u8, u8)) -> bool {
+    // Built from octal digits, as quickcheck favours small integers.
+    let (special, owner, group, others) = digits;
+    let mode = [special, owner, group, others]
+        .iter()
+        .fold(0, |mode, &digit| mode << 3 | u32::from(digit % 8));
+    let perms = Perms::try_from(mode).unwrap();
+
+    Perms::from_octal_str(&format!("{:o}", u32::from(perms))) == Ok(perms)
+        && perms.to_string().parse::<Perms>() == Ok(perms)
+        && Perms::try_from(u32::from(perms)) == Ok(perms)
+}
+
+#[test]
+fn perms_class_all() {
+    assert_eq!(
+        Perms::OWNER_ALL,
+        Perms::OWNER_READ | Perms::OWNER_WRITE | Perms::OWNER_EXEC
+    );
+    assert_eq!(
+        Perms::GROUP_ALL,
+        Perms::GROUP_READ | Perms::GROUP_WRITE | Perms::GROUP_EXEC
+    );
+    assert_eq!(
+        Perms::OTHERS_ALL,
+        Perms::OTHERS_READ | Perms::OTHERS_WRITE | Perms::OTHERS_EXEC
+    );
+    assert_eq!(
+        Perms::ALL,
+        Perms::OWNER_ALL | Perms::GROUP_ALL | Perms::OTHERS_ALL
+    );
+}
+
+#[test]
+fn perms_openness() {
+    assert_eq!(Perms::NONE.openness(), 0);
+    assert_eq!(Perms::OWNER_ALL.openness(), 0);
+    assert_eq!(Perms::STICKY_BIT.openness(), 0);
+    assert_eq!(Perms::try_from(0o755).unwrap().openness(), 4);
+    assert_eq!(Perms::try_from(0o775).unwrap().openness(), 8);
+    assert_eq!(Perms::try_from(0o777).unwrap().openness(), 16);
+    assert_eq!(Perms::try_from(0o6755).unwrap().openness(), 32);
+    assert_eq!(Perms::MASK.openness(), 44);
+
+    // Granting more never lowers the score.
+    for mode in 0..=0o7777 {
+        let perms = Perms::try_from(mode).unwrap();
+        assert!(perms.openness() <= (perms | Perms::OTHERS_WRITE).openness());
+    }
+}
+
+#[test]
+fn perms_parse_absolute_symbolic() {
+    let parse = Perms::parse_absolute_symbolic;
+
+    assert_eq!(parse("u=rw,g=r,o=r"), Ok(Perms::try_from(0o644).unwrap()));
+    assert_eq!(parse("u=rwx"), Ok(Perms::OWNER_ALL));
+    assert_eq!(parse("a=rwx"), Ok(Perms::ALL));
+    assert_eq!(parse("=r"), Ok(Perms::try_from(0o444).unwrap()));
+    assert_eq!(parse("go=rx"), Ok(Perms::try_from(0o055).unwrap()));
+    assert_eq!(parse("a="), Ok(Perms::NONE));
+    assert_eq!(parse("u=rwx,u=r"), Ok(Perms::OWNER_READ));
+    assert_eq!(parse("ug=xs,o=t"), Ok(Perms::try_from(0o7110).unwrap()));
+    assert_eq!(parse("o=s,u=t"), Ok(Perms::NONE));
+
+    assert!(parse("").is_err());
+    assert!(parse("u+x").is_err());
+    assert!(parse("u-x").is_err());
+    assert!(parse("u=r,").is_err());
+    assert!(parse("u=rz").is_err());
+    assert!(parse("z=r").is_err());
+    assert!(parse("u=r=w").is_err());
+}
+
+#[test]
+fn perms_effective_create_mode() {
+    let mode = |requested: u32, umask: u32| {
+        u32::from(Perms::effective_create_mode(
+            Perms::try_from(requested).unwrap(),
+            Perms::try_from(umask).unwrap(),
+        ))
+    };
+
+    let cases = [
+        (0o666, 0o022, 0o644),
+        (0o777, 0o022, 0o755),
+        (0o777, 0o077, 0o700),
+        (0o666, 0o000, 0o666),
+        (0o777, 0o777, 0o000),
+        (0o4755, 0o022, 0o4755),
+        (0o4777, 0o777, 0o4000),
+        (0o2775, 0o002, 0o2775),
+        (0o2777, 0o070, 0o2707),
+        (0o1777, 0o022, 0o1755),
+        (0o1777, 0o777, 0o1000),
+        (0o7777, 0o7777, 0o7000),
+        (0o0755, 0o7000, 0o0755),
+        (0o0000, 0o7777, 0o0000),
+    ];
+
+    for &(requested, umask, expected) in cases.iter() {
+        assert_eq!(
+            mode(requested, umask),
+            expected,
+            "{:o} {:o}",
+            requested,
+            umask
+        );
+    }
+}
+
+#[test]
+fn perms_symbolic_cells() {
+    assert_eq!(Perms::NONE.symbolic_cells(), [SymCell::Unset; 9]);
+    assert_eq!(
+        Perms::try_from(0o7616).unwrap().symbolic_cells(),
+        [
+            SymCell::Read,
+            SymCell::Write,
+            SymCell::SetidNoExec,
+            SymCell::Unset,
+            SymCell::Unset,
+            SymCell::SetidExec,
+            SymCell::Read,
+            SymCell::Write,
+            SymCell::StickyNoExec,
+        ],
+    );
+
+    for mode in 0..=0o7777 {
+        let perms = Perms::try_from(mode).unwrap();
+        let cells: String = perms.symbolic_cells().iter().map(|c| c.as_char()).collect();
+
+        assert_eq!(cells, perms.to_string());
+    }
+}
+
+#[test]
+fn perms_octal_digits() {
+    assert_eq!(Perms::NONE.octal_digits(), [0, 0, 0, 0]);
+    assert_eq!(Perms::MASK.octal_digits(), [7, 7, 7, 7]);
+    assert_eq!(
+        Perms::try_from(0o1640).unwrap().octal_digits(),
+        [1, 6, 4, 0]
+    );
+
+    assert!(Perms::from_octal_digits([0, 8, 0, 0]).is_err());
+    assert!(Perms::from_octal_digits([0, 0, 0, 255]).is_err());
+
+    for mode in 0..=0o7777 {
+        let perms = Perms::try_from(mode).unwrap();
+        assert_eq!(Perms::from_octal_digits(perms.octal_digits()), Ok(perms));
+    }
+}
+
+#[test]
+fn perms_clear_setid_if_writable() {
+    let clear = |mode| u32::from(Perms::try_from(mode).unwrap().clear_setid_if_writable());
+
+    assert_eq!(clear(0o4755), 0o4755);
+    assert_eq!(clear(0o6755), 0o6755);
+    assert_eq!(clear(0o4775), 0o0775);
+    assert_eq!(clear(0o2757), 0o0757);
+    assert_eq!(clear(0o7777), 0o1777);
+    assert_eq!(clear(0o1777), 0o1777);
+    assert_eq!(clear(0o0666), 0o0666);
+}
+
+#[test]
+fn perms_with_mask() {
+    assert_eq!(
+        Perms::with_mask(0o644, 0o777),
+        Ok(Perms::try_from(0o644).unwrap())
+    );
+    assert!(Perms::with_mask(0o4644, 0o777).is_err());
+    assert!(Perms::with_mask(0o644, 0).is_err());
+
+    let extended = Perms::with_mask(0o40000 | 0o755, 0o47777).unwrap();
+    assert_eq!(u32::from(extended), 0o40755);
+    assert_eq!(extended.to_string(), "rwxr-xr-x");
+    assert_eq!(u32::from(extended & Perms::MASK), 0o755);
+
+    // Methods limited to the 12 permission bits ignore the extended bits.
+    let bytes = extended.to_canonical_bytes();
+    assert_eq!(bytes, Perms::try_from(0o755).unwrap().to_canonical_bytes());
+    assert_eq!(
+        Perms::from_canonical_bytes(bytes),
+        Ok(extended & Perms::MASK)
+    );
+    assert_eq!(extended.octal_digits(), [0, 7, 5, 5]);
+    assert!(extended.matches_mode(0o40755));
+    assert!(extended.matches_mode(0o100755));
+    assert!(!extended.matches_mode(0o40644));
+
+    for mode in 0..=0o7777 {
+        assert_eq!(
+            Perms::with_mask(mode, Perms::MASK.into()),
+            Perms::try_from(mode)
+        );
+    }
+}
+
+#[test]
+fn perms_display_padding() {
+    let perms = Perms::try_from(0o4755).unwrap();
+
+    assert_eq!(format!("{:>12}", perms), "   rwsr-xr-x");
+    assert_eq!(format!("{:<12}|", perms), "rwsr-xr-x   |");
+    assert_eq!(format!("{:*^13}", perms), "**rwsr-xr-x**");
+    assert_eq!(format!("{:4}", perms), "rwsr-xr-x");
+    assert_eq!(format!("{:.3}", perms), "rws");
+}
+
+#[test]
+fn perms_as_u32() {
+    fn bits<T: AsRef<u32>>(value: &T) -> u32 {
+        *value.as_ref()
+    }
+
+    for mode in 0..=0o7777 {
+        let perms = Perms::try_from(mode).unwrap();
+
+        assert_eq!(perms.as_u32(), mode);
+        assert_eq!(u32::from(&perms), mode);
+        assert_eq!(bits(&perms), mode);
+    }
+}
+
+#[test]
+fn perms_builder() {
+    assert_eq!(Perms::builder().build(), Perms::NONE);
+
+    let all = Perms::builder()
+        .owner_read()
+        .owner_write()
+        .owner_exec()
+        .group_read()
+        .group_write()
+        .group_exec()
+        .others_read()
+        .others_write()
+        .others_exec()
+        .setuid()
+        .setgid()
+        .sticky()
+        .build();
+    assert_eq!(all, Perms::MASK);
+
+    let rwx: PermsBuilder = Perms::builder().owner_rwx().group_rwx().others_rwx();
+    assert_eq!(rwx.build(), Perms::ALL);
+
+    assert_eq!(
+        Perms::builder()
+            .owner_rwx()
+            .group_read()
+            .others_read()
+            .build(),
+        Perms::try_from(0o744).unwrap()
+    );
+    assert_eq!(
+        Perms::builder().grant(Class::Others, Op::Write).build(),
+        Perms::OTHERS_WRITE
+    );
+}
+
+#[test]
+fn perms_normalize() {
+    let normalize = |mode| u32::from(Perms::try_from(mode).unwrap().normalize());
+
+    assert_eq!(normalize(0o4644), 0o0644);
+    assert_eq!(normalize(0o4744), 0o4744);
+    assert_eq!(normalize(0o2644), 0o0644);
+    assert_eq!(normalize(0o2654), 0o2654);
+    assert_eq!(normalize(0o7666), 0o1666);
+    assert_eq!(normalize(0o7777), 0o7777);
+
+    for mode in 0..=0o7777 {
+        let perms = Perms::try_from(mode).unwrap();
+
+        assert_eq!(perms.normalize().normalize(), perms.normalize());
+        assert_eq!(perms.has_redundant_setid(), perms.to_string().contains('S'));
+    }
+}
+
+#[test]
+fn perms_matrix() {
+    assert_eq!(Perms::NONE.to_matrix(), [[false; 3]; 3]);
+    assert_eq!(Perms::MASK.to_matrix(), [[true; 3]; 3]);
+    assert_eq!(Perms::from_matrix([[true; 3]; 3]), Perms::ALL);
+
+    let mut matrix = [[false; 3]; 3];
+    matrix[1][2] = true;
+    assert_eq!(Perms::from_matrix(matrix), Perms::GROUP_EXEC);
+
+    for mode in 0..=0o7777 {
+        let perms = Perms::try_from(mode).unwrap();
+        assert_eq!(Perms::from_matrix(perms.to_matrix()), perms & Perms::ALL);
+    }
+}
+
+#[test]
+fn perms_union_intersection_all() {
+    assert_eq!(Perms::union_all(None), Perms::NONE);
+    assert_eq!(Perms::intersection_all(None), Perms::MASK);
+
+    let one = Perms::try_from(0o1755).unwrap();
+    assert_eq!(Perms::union_all(Some(one)), one);
+    assert_eq!(Perms::intersection_all(Some(one)), one);
+
+    let modes: Vec<_> = (0..=0o7777)
+        .step_by(0o111)
+        .map(|m| Perms::try_from(m).unwrap())
+        .collect();
+    assert_eq!(Perms::union_all(modes.iter().copied()), Perms::MASK);
+    assert_eq!(Perms::intersection_all(modes.iter().copied()), Perms::NONE);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn perms_to_string_with_style() {
+    let special = Perms::try_from(0o7000).unwrap();
+
+    assert_eq!(
+        special.to_string_with_style(SymStyle::Standard),
+        "--S--S--T"
+    );
+    assert_eq!(
+        special.to_string_with_style(SymStyle::Uppercase),
+        "--S--S--T"
+    );
+    assert_eq!(special.to_string_with_style(SymStyle::Hidden), "---------");
+
+    let special = Perms::try_from(0o7111).unwrap();
+
+    assert_eq!(
+        special.to_string_with_style(SymStyle::Standard),
+        "--s--s--t"
+    );
+    assert_eq!(
+        special.to_string_with_style(SymStyle::Uppercase),
+        "--S--S--T"
+    );
+    assert_eq!(special.to_string_with_style(SymStyle::Hidden), "--x--x--x");
+}
+
+#[quickcheck]
+fn qc_perms_default_style_matches_display(perms: u32) -> bool {
+    let perms = Perms::try_from(perms & 0o7777).unwrap();
+    let mut s = String::new();
+
+    perms
+        .write_symbolic_with_style(&mut s, SymStyle::default())
+        .is_ok()
+        && s == perms.to_string()
+}
+
+#[test]
+fn perms_from_readonly() {
+    assert_eq!(Perms::from_readonly(true), Perms::try_from(0o444).unwrap());
+    assert_eq!(Perms::from_readonly(false), Perms::try_from(0o644).unwrap());
+}
+
+#[quickcheck]
+fn qc_perms_from_readonly_roundtrip(readonly: bool) -> bool {
+    Perms::from_readonly(readonly).is_effectively_readonly() == readonly
+}
+
+#[quickcheck]
+fn qc_perms_is_effectively_readonly(perms: u32) -> bool {
+    let perms = Perms::try_from(perms & 0o7777).unwrap();
+
+    perms.is_effectively_readonly()
+        == [Class::Owner, Class::Group, Class::Others]
+            .iter()
+            .all(|&class| !perms.allows(class, Op::Write))
+}
+
+#[quickcheck]
+fn qc_perms_matches_mode(perms: u32, file_type: u32) -> bool {
+    let raw = perms & 0o7777 | file_type & !0o7777;
+    let perms = Perms::try_from(perms & 0o7777).unwrap();
+
+    perms.matches_mode(raw) && !(perms ^ Perms::OTHERS_EXEC).matches_mode(raw)
+}
@@ -0,0 +1,98 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use nano_leb128::{SLEB128, ULEB128};
+
+/// Unsigned values dominated by single byte encodings, with some two and
+/// three byte encodings mixed in.
+fn small_unsigned() -> Vec<u64> {
+    (0..1024)
+        .map(|i| if i % 8 == 0 { i * 37 } else { i % 128 })
+        .collect()
+}
+
+/// Signed values dominated by single byte encodings, with some two and three
+/// byte encodings mixed in.
+fn small_signed() -> Vec<i64> {
+    (0..1024)
+        .map(|i| {
+            if i % 8 == 0 {
+                (i - 512) * 37
+            } else {
+                i % 128 - 64
+            }
+        })
+        .collect()
+}
+
+fn uleb128(c: &mut Criterion) {
+    let values = small_unsigned();
+
+    let mut encoded = vec![0; values.len() * 10];
+    let mut pos = 0;
+    for &val in &values {
+        pos += ULEB128::from(val).write_into(&mut encoded[pos..]).unwrap();
+    }
+    encoded.truncate(pos);
+
+    c.bench_function("uleb128 write_into small", |b| {
+        let mut buf = vec![0; values.len() * 10];
+        b.iter(|| {
+            let mut pos = 0;
+            for &val in &values {
+                pos += ULEB128::from(black_box(val))
+                    .write_into(&mut buf[pos..])
+                    .unwrap();
+            }
+            pos
+        })
+    });
+
+    c.bench_function("uleb128 read_from small", |b| {
+        b.iter(|| {
+            let mut pos = 0;
+            while pos < encoded.len() {
+                let (val, len) = ULEB128::read_from(black_box(&encoded[pos..])).unwrap();
+                black_box(val);
+                pos += len;
+            }
+        })
+    });
+}
+
+fn sleb128(c: &mut Criterion) {
+    let values = small_signed();
+
+    let mut encoded = vec![0; values.len() * 10];
+    let mut pos = 0;
+    for &val in &values {
+        pos += SLEB128::from(val).write_into(&mut encoded[pos..]).unwrap();
+    }
+    encoded.truncate(pos);
+
+    c.bench_function("sleb128 write_into small", |b| {
+        let mut buf = vec![0; values.len() * 10];
+        b.iter(|| {
+            let mut pos = 0;
+            for &val in &values {
+                pos += SLEB128::from(black_box(val))
+                    .write_into(&mut buf[pos..])
+                    .unwrap();
+            }
+            pos
+        })
+    });
+
+    c.bench_function("sleb128 read_from small", |b| {
+        b.iter(|| {
+            let mut pos = 0;
+            while pos < encoded.len() {
+                let (val, len) = SLEB128::read_from(black_box(&encoded[pos..])).unwrap();
+                black_box(val);
+                pos += len;
+            }
+        })
+    });
+}
+
+criterion_group!(benches, uleb128, sleb128);
+criterion_main!(benches);
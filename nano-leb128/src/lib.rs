@@ -48,12 +48,20 @@
 //! assert_eq!(u64::from(decompressed), value);
 //! ```
 //!
+//! # Byte order
+//!
+//! LEB128 is a byte oriented encoding: the least significant group of seven
+//! bits is always written first, regardless of the endianness of the host.
+//! Values are only ever manipulated arithmetically (shifts, masks, and
+//! truncating casts), so encodings are identical on big and little endian
+//! targets.
+//!
 //! # Features
 //!
 //! * `std` (enabled by default)
 //!
-//!    This enables extensions that are only available with the Rust standard
-//!    library.
+//!   This enables extensions that are only available with the Rust standard
+//!   library.
 //!
 //! * `std_io_ext`
 //!
@@ -68,8 +76,15 @@
 //!   implementors of the traits in [`byteio`]. This feature does not require
 //!   the `std` feature.
 //!
+//! * `bytes_ext`
+//!
+//!   Adds methods for reading/writing LEB128 compressed values from
+//!   implementors of the traits in [`bytes`]. This feature does not require
+//!   the `std` feature.
+//!
 //! [`std::io`]: https://doc.rust-lang.org/std/io/index.html
 //! [`byteio`]: https://docs.rs/byteio
+//! [`bytes`]: https://docs.rs/bytes
 
 #![no_std]
 #![allow(clippy::nonminimal_bool)]
@@ -79,7 +94,7 @@
 #[cfg(feature = "std")]
 extern crate std;
 
-use core::mem;
+use core::{convert::TryFrom, iter, mem, ops, time::Duration};
 
 use byteio::{ReadBytes, ReadBytesExt, WriteBytes, WriteBytesExt};
 
@@ -133,17 +148,473 @@ impl SLEB128 {
     /// On success this will return the decompressed value and the number of
     /// bytes that were read.
     pub fn read_from(buf: &[u8]) -> Result<(Self, usize), LEB128DecodeError> {
+        // Values of a single byte are common enough to skip the general loop.
+        if let Some(&byte) = buf.first() {
+            if byte & LEB128_HIGH_ORDER_BIT == 0 {
+                return Ok((Self(i64::from((byte << 1) as i8 >> 1)), 1));
+            }
+        }
+
         <Self as LEB128>::read_from(buf)
     }
 
+    /// Attempts to read a signed LEB128 compressed value from a buffer,
+    /// rejecting encodings which are not the shortest possible.
+    ///
+    /// Encodings padded with redundant bytes, such as `[0x80, 0x00]` for
+    /// zero, are accepted by [`read_from`] but are non-canonical under e.g.
+    /// the DWARF and WebAssembly specifications. For these this returns
+    /// [`LEB128DecodeError::Overlong`].
+    ///
+    /// On success this will return the decompressed value and the number of
+    /// bytes that were read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::{LEB128DecodeError, SLEB128};
+    ///
+    /// assert!(SLEB128::read_from_canonical(&[0x00]).is_ok());
+    ///
+    /// assert_eq!(
+    ///     SLEB128::read_from_canonical(&[0x80, 0x00]),
+    ///     Err(LEB128DecodeError::Overlong),
+    /// );
+    /// ```
+    ///
+    /// [`read_from`]: #method.read_from
+    /// [`LEB128DecodeError::Overlong`]: enum.LEB128DecodeError.html#variant.Overlong
+    pub fn read_from_canonical(buf: &[u8]) -> Result<(Self, usize), LEB128DecodeError> {
+        let (value, len) = Self::read_from(buf)?;
+
+        if len > value.encoded_len() {
+            return Err(LEB128DecodeError::Overlong);
+        }
+
+        Ok((value, len))
+    }
+
+    /// Attempts to read exactly `K` consecutive signed LEB128 compressed
+    /// values from a buffer.
+    ///
+    /// On success this will return the decompressed values and the total
+    /// number of bytes that were read. Any bytes following the last value
+    /// are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::SLEB128;
+    ///
+    /// let (values, len) = SLEB128::read_array_from::<2>(&[0x7F, 0xC0, 0xBB, 0x78]).unwrap();
+    ///
+    /// assert_eq!(values, [-1, -123456]);
+    /// assert_eq!(len, 4);
+    /// ```
+    pub fn read_array_from<const K: usize>(
+        buf: &[u8],
+    ) -> Result<([i64; K], usize), LEB128DecodeError> {
+        let mut values = [0; K];
+        let mut len = 0;
+
+        for value in values.iter_mut() {
+            let (Self(decoded), n) = Self::read_from(&buf[len..])?;
+            *value = decoded;
+            len += n;
+        }
+
+        Ok((values, len))
+    }
+
+    /// Reads every signed LEB128 compressed value in a buffer, calling `f`
+    /// with each in turn.
+    ///
+    /// On success this will return the total number of bytes that were read,
+    /// which is always `buf.len()`. If the buffer ends with a truncated value
+    /// then [`LEB128DecodeError::BufferOverflow`] is returned, after `f` has
+    /// been called with every complete value before it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::SLEB128;
+    ///
+    /// let mut sum = 0;
+    /// let len = SLEB128::for_each_in(&[0x02, 0xE5, 0x8E, 0x26], |value| sum += value).unwrap();
+    ///
+    /// assert_eq!(sum, 624487);
+    /// assert_eq!(len, 4);
+    /// ```
+    ///
+    /// [`LEB128DecodeError::BufferOverflow`]: enum.LEB128DecodeError.html#variant.BufferOverflow
+    pub fn for_each_in<F: FnMut(i64)>(buf: &[u8], mut f: F) -> Result<usize, LEB128DecodeError> {
+        let mut len = 0;
+
+        while len < buf.len() {
+            let (Self(value), n) = Self::read_from(&buf[len..])?;
+            f(value);
+            len += n;
+        }
+
+        Ok(len)
+    }
+
+    /// Returns `true` if `buf` contains exactly one complete signed LEB128
+    /// compressed value, with no trailing bytes.
+    ///
+    /// The value must not overflow an `i64`. As with [`read_from`], over-long
+    /// encodings which pad the value with redundant bytes are valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::SLEB128;
+    ///
+    /// assert!(SLEB128::is_valid_encoding(&[0x00]));
+    /// assert!(SLEB128::is_valid_encoding(&[0x80, 0x00]));
+    ///
+    /// assert!(!SLEB128::is_valid_encoding(&[]));
+    /// assert!(!SLEB128::is_valid_encoding(&[0x80]));
+    /// assert!(!SLEB128::is_valid_encoding(&[0x00, 0x00]));
+    /// ```
+    ///
+    /// [`read_from`]: #method.read_from
+    pub fn is_valid_encoding(buf: &[u8]) -> bool {
+        match Self::read_from(buf) {
+            Ok((_, len)) => len == buf.len(),
+            Err(_) => false,
+        }
+    }
+
+    /// Attempts to read a signed LEB128 compressed value from a buffer,
+    /// treating an empty buffer as an encoded zero.
+    ///
+    /// This is useful for formats in which an absent field means zero. An
+    /// empty buffer returns a value of zero and a length of `0`, while a
+    /// non-empty buffer is decoded as by [`read_from`], so a truncated value
+    /// is still reported as [`LEB128DecodeError::BufferOverflow`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::SLEB128;
+    ///
+    /// let (val, len) = SLEB128::read_from_or_default(&[]).unwrap();
+    /// assert_eq!((i64::from(val), len), (0, 0));
+    ///
+    /// assert!(SLEB128::read_from_or_default(&[0x80]).is_err());
+    /// ```
+    ///
+    /// [`read_from`]: #method.read_from
+    /// [`LEB128DecodeError::BufferOverflow`]: enum.LEB128DecodeError.html#variant.BufferOverflow
+    pub fn read_from_or_default(buf: &[u8]) -> Result<(Self, usize), LEB128DecodeError> {
+        if buf.is_empty() {
+            Ok((Self(0), 0))
+        } else {
+            Self::read_from(buf)
+        }
+    }
+
+    /// Attempts to read a signed LEB128 compressed value from a buffer,
+    /// reporting the number of bytes that were read even on failure.
+    ///
+    /// On failure the error is returned along with the number of bytes that
+    /// were examined: the whole buffer for
+    /// [`LEB128DecodeError::BufferOverflow`], or up to and including the byte
+    /// which overflowed for [`LEB128DecodeError::IntegerOverflow`]. This
+    /// allows a streaming decoder to skip past a malformed value.
+    ///
+    /// [`LEB128DecodeError::BufferOverflow`]: enum.LEB128DecodeError.html#variant.BufferOverflow
+    /// [`LEB128DecodeError::IntegerOverflow`]: enum.LEB128DecodeError.html#variant.IntegerOverflow
+    pub fn read_from_reporting(buf: &[u8]) -> Result<(Self, usize), (LEB128DecodeError, usize)> {
+        <Self as LEB128>::read_from_reporting(buf)
+    }
+
+    /// Reads a signed LEB128 compressed value from a buffer, clamping values
+    /// which do not fit into an `i64` to `i64::MIN` or `i64::MAX`.
+    ///
+    /// Unlike [`read_from`] an over-large value does not produce an error.
+    /// The whole compressed value is still consumed, so the number of bytes
+    /// that were read may be used to continue with any following values. An
+    /// error is only returned if the buffer ends before the value does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::SLEB128;
+    ///
+    /// let buf = [0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7E];
+    ///
+    /// let (val, len) = SLEB128::read_from_saturating(&buf).unwrap();
+    ///
+    /// assert_eq!(i64::from(val), i64::MIN);
+    /// assert_eq!(len, 11);
+    /// ```
+    ///
+    /// [`read_from`]: #method.read_from
+    pub fn read_from_saturating(buf: &[u8]) -> Result<(Self, usize), LEB128DecodeError> {
+        let (value, overflow, len) = sleb128_decode_lossy(buf)?;
+
+        let value = match overflow {
+            Some(true) => i64::MIN,
+            Some(false) => i64::MAX,
+            None => value,
+        };
+
+        Ok((Self(value), len))
+    }
+
+    /// Reads a signed LEB128 compressed value from a buffer, keeping only the
+    /// low 64 bits of values which do not fit into an `i64`.
+    ///
+    /// As with [`read_from_saturating`] the whole compressed value is
+    /// consumed, and an error is only returned if the buffer ends before the
+    /// value does.
+    ///
+    /// [`read_from_saturating`]: #method.read_from_saturating
+    pub fn read_from_wrapping(buf: &[u8]) -> Result<(Self, usize), LEB128DecodeError> {
+        let (value, _, len) = sleb128_decode_lossy(buf)?;
+
+        Ok((Self(value), len))
+    }
+
     /// Attempts to write a value into a buffer using signed LEB128
     /// compression.
     ///
     /// On success this will return the number of bytes that were written.
     pub fn write_into(self, buf: &mut [u8]) -> Result<usize, LEB128EncodeError> {
+        // Values of a single byte are common enough to skip the general loop.
+        if (-64..64).contains(&self.0) {
+            let byte = buf
+                .first_mut()
+                .ok_or(LEB128EncodeError::BufferOverflow { needed: 1 })?;
+            *byte = self.0 as u8 & !LEB128_HIGH_ORDER_BIT;
+
+            return Ok(1);
+        }
+
         <Self as LEB128>::write_into(self, buf)
     }
 
+    /// Writes the value into a buffer using signed LEB128 compression,
+    /// guaranteeing the shortest possible encoding.
+    ///
+    /// The encoding produced by [`write_into`] is always the shortest
+    /// possible, so this behaves identically. It exists to name the guarantee
+    /// for callers that rely on it, and checks it in debug builds.
+    ///
+    /// On success this will return the number of bytes that were written,
+    /// which is always equal to [`encoded_len`].
+    ///
+    /// [`write_into`]: #method.write_into
+    /// [`encoded_len`]: #method.encoded_len
+    pub fn write_into_canonical(self, buf: &mut [u8]) -> Result<usize, LEB128EncodeError> {
+        let len = self.write_into(buf)?;
+        debug_assert_eq!(len, self.encoded_len());
+
+        Ok(len)
+    }
+
+    /// Writes the value into a buffer using signed LEB128 compression,
+    /// padded to exactly `pad_to` bytes.
+    ///
+    /// The value is padded with redundant continuation bytes, which is legal
+    /// but not the shortest possible encoding. This allows a fixed number of
+    /// bytes to be reserved for a value which is patched in later, as done
+    /// by linkers. Padded values of up to 10 bytes are decoded by
+    /// [`read_from`], but are rejected by [`read_from_canonical`].
+    ///
+    /// If the value needs more than `pad_to` bytes, or the buffer is shorter
+    /// than `pad_to` bytes, then [`LEB128EncodeError::BufferOverflow`] is
+    /// returned and the buffer is left untouched. If `pad_to` is more than
+    /// 10 bytes then [`LEB128EncodeError::Overlong`] is returned, as the
+    /// padded value could not be decoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::SLEB128;
+    ///
+    /// let mut buf = [0; 3];
+    ///
+    /// assert_eq!(SLEB128::from(5).write_into_padded(&mut buf, 3).unwrap(), 3);
+    /// assert_eq!(buf, [0x85, 0x80, 0x00]);
+    /// ```
+    ///
+    /// [`read_from`]: #method.read_from
+    /// [`read_from_canonical`]: #method.read_from_canonical
+    /// [`LEB128EncodeError::BufferOverflow`]: enum.LEB128EncodeError.html#variant.BufferOverflow
+    /// [`LEB128EncodeError::Overlong`]: enum.LEB128EncodeError.html#variant.Overlong
+    pub fn write_into_padded(
+        self,
+        buf: &mut [u8],
+        pad_to: usize,
+    ) -> Result<usize, LEB128EncodeError> {
+        if pad_to > 10 {
+            return Err(LEB128EncodeError::Overlong);
+        }
+
+        let needed = self.encoded_len();
+
+        if needed > pad_to {
+            return Err(LEB128EncodeError::BufferOverflow { needed });
+        }
+
+        let buf = buf
+            .get_mut(..pad_to)
+            .ok_or(LEB128EncodeError::BufferOverflow { needed: pad_to })?;
+        let Self(mut value) = self;
+
+        for (i, byte) in buf.iter_mut().enumerate() {
+            *byte = (value as u8) & !LEB128_HIGH_ORDER_BIT;
+            value >>= 7;
+
+            if i + 1 < pad_to {
+                *byte |= LEB128_HIGH_ORDER_BIT;
+            }
+        }
+
+        Ok(pad_to)
+    }
+
+    /// Reinterprets the bits of the value as an unsigned integer.
+    ///
+    /// This is a bit-preserving reinterpretation (`i64 as u64`), not a numeric
+    /// conversion: negative values become large unsigned values. Note that
+    /// the compressed encodings of the two values generally differ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::SLEB128;
+    ///
+    /// assert_eq!(u64::from(SLEB128::from(-1).reinterpret_unsigned()), u64::MAX);
+    /// assert_eq!(u64::from(SLEB128::from(5).reinterpret_unsigned()), 5);
+    /// ```
+    pub const fn reinterpret_unsigned(self) -> ULEB128 {
+        ULEB128(self.0 as u64)
+    }
+
+    /// Maps the value to an unsigned value using zigzag encoding, as used by
+    /// the protobuf `sint32` and `sint64` types.
+    ///
+    /// Values of small magnitude map to small unsigned values regardless of
+    /// sign: `0, -1, 1, -2, 2, ...` map to `0, 1, 2, 3, 4, ...`. This differs
+    /// from the two's complement sign extension used by signed LEB128.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::SLEB128;
+    ///
+    /// assert_eq!(u64::from(SLEB128::from(-1).to_zigzag()), 1);
+    /// assert_eq!(u64::from(SLEB128::from(1).to_zigzag()), 2);
+    /// assert_eq!(u64::from(SLEB128::from(i64::MIN).to_zigzag()), u64::MAX);
+    /// ```
+    pub const fn to_zigzag(self) -> ULEB128 {
+        let Self(value) = self;
+
+        ULEB128(((value << 1) ^ (value >> 63)) as u64)
+    }
+
+    /// Maps a zigzag encoded unsigned value back to a signed value.
+    ///
+    /// This is the inverse of [`to_zigzag`].
+    ///
+    /// [`to_zigzag`]: #method.to_zigzag
+    pub const fn from_zigzag(uleb128: ULEB128) -> Self {
+        let ULEB128(value) = uleb128;
+
+        Self((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+
+    /// Attempts to read a zigzag encoded value from a buffer, as by
+    /// [`ULEB128::read_from`].
+    ///
+    /// On success this will return the decompressed value and the number of
+    /// bytes that were read. See [`to_zigzag`].
+    ///
+    /// [`ULEB128::read_from`]: struct.ULEB128.html#method.read_from
+    /// [`to_zigzag`]: #method.to_zigzag
+    pub fn read_from_zigzag(buf: &[u8]) -> Result<(Self, usize), LEB128DecodeError> {
+        ULEB128::read_from(buf).map(|(value, len)| (Self::from_zigzag(value), len))
+    }
+
+    /// Attempts to write a value into a buffer using zigzag encoding followed
+    /// by unsigned LEB128 compression.
+    ///
+    /// On success this will return the number of bytes that were written.
+    /// See [`to_zigzag`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::SLEB128;
+    ///
+    /// let mut buf = [0; 2];
+    ///
+    /// assert_eq!(SLEB128::from(-64).write_into_zigzag(&mut buf).unwrap(), 1);
+    /// assert_eq!(buf[0], 0x7F);
+    ///
+    /// assert_eq!(SLEB128::from(64).write_into_zigzag(&mut buf).unwrap(), 2);
+    /// assert_eq!(buf, [0x80, 0x01]);
+    /// ```
+    ///
+    /// [`to_zigzag`]: #method.to_zigzag
+    pub fn write_into_zigzag(self, buf: &mut [u8]) -> Result<usize, LEB128EncodeError> {
+        self.to_zigzag().write_into(buf)
+    }
+
+    /// Returns the number of bytes in the signed LEB128 compression of the
+    /// value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::SLEB128;
+    ///
+    /// assert_eq!(SLEB128::from(63).encoded_len(), 1);
+    /// assert_eq!(SLEB128::from(64).encoded_len(), 2);
+    /// assert_eq!(SLEB128::from(-123456).encoded_len(), 3);
+    /// assert_eq!(SLEB128::from(i64::MIN).encoded_len(), 10);
+    /// ```
+    pub const fn encoded_len(self) -> usize {
+        let Self(value) = self;
+
+        // The number of significant bits, including the sign bit.
+        let bits = if value < 0 {
+            65 - (!value).leading_zeros()
+        } else {
+            65 - value.leading_zeros()
+        };
+
+        bits.div_ceil(7) as usize
+    }
+
+    /// Attempts to read a signed LEB128 compressed value from a source
+    /// function which yields one byte per call.
+    ///
+    /// The source should return `None` once no more bytes are available.
+    ///
+    /// On success this will return the decompressed value and the number of
+    /// bytes that were read.
+    pub fn decode_from_fn<F: FnMut() -> Option<u8>>(
+        next: F,
+    ) -> Result<(Self, usize), LEB128DecodeError> {
+        <Self as LEB128>::decode_from_fn(next)
+    }
+
+    /// Attempts to write a value into a sink function using signed LEB128
+    /// compression.
+    ///
+    /// The sink is called once per byte, and should return `false` if the
+    /// byte could not be accepted.
+    ///
+    /// On success this will return the number of bytes that were written.
+    pub fn encode_to_fn<G: FnMut(u8) -> bool>(self, push: G) -> Result<usize, LEB128EncodeError> {
+        <Self as LEB128>::encode_to_fn(self, push)
+    }
+
     /// Attempts to read a signed LEB128 compressed value from an implementor
     /// of [`std::io::Read`].
     ///
@@ -158,6 +629,84 @@ impl SLEB128 {
         <Self as LEB128>::read_from_std_io(reader)
     }
 
+    /// Attempts to read a signed LEB128 compressed value from an
+    /// implementor of [`std::io::Read`], using `scratch` as the intermediate
+    /// buffer.
+    ///
+    /// **Note**: Requires the feature `std_io_ext`.
+    ///
+    /// This behaves exactly like [`read_from_std_io`], but allows a single
+    /// buffer to be reused across many calls. `scratch` is cleared on entry,
+    /// and holds the bytes that were read on return.
+    ///
+    /// On success this will return the decompressed value and the number of
+    /// bytes that were read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::SLEB128;
+    ///
+    /// let mut reader = &[0x01, 0x02][..];
+    /// let mut scratch = Vec::new();
+    ///
+    /// let (a, _) = SLEB128::read_from_std_io_with(&mut reader, &mut scratch).unwrap();
+    /// let (b, _) = SLEB128::read_from_std_io_with(&mut reader, &mut scratch).unwrap();
+    ///
+    /// assert_eq!((i64::from(a), i64::from(b)), (1, 2));
+    /// ```
+    ///
+    /// [`std::io::Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
+    /// [`read_from_std_io`]: #method.read_from_std_io
+    #[cfg(feature = "std_io_ext")]
+    pub fn read_from_std_io_with<R: ::std::io::Read>(
+        reader: R,
+        scratch: &mut ::std::vec::Vec<u8>,
+    ) -> ::std::io::Result<(Self, usize)> {
+        <Self as LEB128>::read_from_std_io_with(reader, scratch)
+    }
+
+    /// Attempts to read a signed LEB128 compressed value from an implementor
+    /// of [`std::io::Read`] without allocating.
+    ///
+    /// **Note**: Requires the feature `std_io_ext`.
+    ///
+    /// Unlike [`read_from_std_io`] no intermediate buffer is allocated. At
+    /// most 10 bytes are read, the maximum width of a compressed `i64`.
+    ///
+    /// On success this will return the decompressed value and the number of
+    /// bytes that were read.
+    ///
+    /// [`std::io::Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
+    /// [`read_from_std_io`]: #method.read_from_std_io
+    #[cfg(feature = "std_io_ext")]
+    pub fn read_from_std_io_no_alloc<R: ::std::io::Read>(
+        reader: R,
+    ) -> ::std::io::Result<(Self, usize)> {
+        <Self as LEB128>::read_from_std_io_no_alloc(reader)
+    }
+
+    /// Attempts to read a signed LEB128 compressed value from an implementor
+    /// of [`std::io::BufRead`].
+    ///
+    /// **Note**: Requires the feature `std_io_ext`.
+    ///
+    /// Unlike [`read_from_std_io`] this scans the reader's internal buffer
+    /// rather than issuing a read per byte, which avoids a syscall per byte
+    /// for buffered sockets and files.
+    ///
+    /// On success this will return the decompressed value and the number of
+    /// bytes that were read.
+    ///
+    /// [`std::io::BufRead`]: https://doc.rust-lang.org/std/io/trait.BufRead.html
+    /// [`read_from_std_io`]: #method.read_from_std_io
+    #[cfg(feature = "std_io_ext")]
+    pub fn read_from_buf_read<R: ::std::io::BufRead>(
+        reader: R,
+    ) -> ::std::io::Result<(Self, usize)> {
+        <Self as LEB128>::read_from_buf_read(reader)
+    }
+
     /// Attempts to write a value into an implementor of [`std::io::Write`]
     /// using signed LEB128 compression.
     ///
@@ -187,6 +736,40 @@ impl SLEB128 {
         <Self as LEB128>::read_from_byteio(reader)
     }
 
+    /// Attempts to read a signed LEB128 compressed value from an
+    /// implementor of [`byteio::ReadBytes`], returning the advanced reader.
+    ///
+    /// **Note**: Requires the feature `byteio_ext`.
+    ///
+    /// This allows several values to be decoded in turn from a reader which
+    /// is passed by value, e.g. a byte slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::{SLEB128, LEB128DecodeError};
+    ///
+    /// fn read_pair(buf: &[u8]) -> Result<(i64, i64), LEB128DecodeError> {
+    ///     let (a, rest) = SLEB128::read_from_byteio_keep(buf)?;
+    ///     let (b, _) = SLEB128::read_from_byteio_keep(rest)?;
+    ///
+    ///     Ok((i64::from(a), i64::from(b)))
+    /// }
+    ///
+    /// assert_eq!(read_pair(&[0x01, 0x02]), Ok((1, 2)));
+    /// ```
+    ///
+    /// [`byteio::ReadBytes`]: https://docs.rs/byteio/latest/trait.ReadBytes.html
+    #[cfg(feature = "byteio_ext")]
+    pub fn read_from_byteio_keep<'a, R: ReadBytes<'a>>(
+        reader: R,
+    ) -> Result<(Self, R), LEB128DecodeError> {
+        let mut reader = ::byteio::Reader::new(reader);
+        let value = Self::leb128_decode(&mut reader)?;
+
+        Ok((value, reader.into_inner()))
+    }
+
     /// Attempts to write a value into an implementor of [`byteio::WriteBytes`]
     /// using signed LEB128 compression.
     ///
@@ -199,53 +782,1586 @@ impl SLEB128 {
     pub fn write_into_byteio<W: WriteBytes>(self, writer: W) -> Result<usize, LEB128EncodeError> {
         <Self as LEB128>::write_into_byteio(self, writer)
     }
-}
-
-/// A value that can be (de)serialized using _unsigned_ LEB128 variable length
-/// compression.
-///
-/// # Examples
-///
-/// Deserializing a value that was serialized using unsigned LEB128 variable
-/// length compression:
-///
-/// ```
-/// use nano_leb128::ULEB128;
-///
-/// let buf = [0xE5, 0x8E, 0x26];
-///
-/// let (val, len) = ULEB128::read_from(&buf).unwrap();
-///
-/// assert_eq!(u64::from(val), 624485);
-/// assert_eq!(len, 3);
-/// ```
-///
-/// Serializing a value using unsigned LEB128 variable length compression:
-///
-/// ```
-/// use nano_leb128::ULEB128;
-///
-/// let mut buf = [0; 3];
-///
-/// assert_eq!(ULEB128::from(624485).write_into(&mut buf).unwrap(), 3);
-/// assert_eq!(buf, [0xE5, 0x8E, 0x26]);
-/// ```
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-pub struct ULEB128(u64);
 
-impl From<ULEB128> for u64 {
-    fn from(uleb128: ULEB128) -> Self {
+    /// Attempts to read a signed LEB128 compressed value from an
+    /// implementor of [`bytes::Buf`].
+    ///
+    /// **Note**: Requires the feature `bytes_ext`.
+    ///
+    /// On success this will return the decompressed value and the number of
+    /// bytes that were read. Reading never goes past [`Buf::remaining`]; if
+    /// the value is truncated then [`LEB128DecodeError::BufferOverflow`] is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use nano_leb128::SLEB128;
+    ///
+    /// let mut buf = Bytes::from_static(&[0x02, 0xE5, 0x8E, 0x26]);
+    ///
+    /// let (a, _) = SLEB128::read_from_bytes(&mut buf).unwrap();
+    /// let (b, _) = SLEB128::read_from_bytes(&mut buf).unwrap();
+    ///
+    /// assert_eq!((i64::from(a), i64::from(b)), (2, 624485));
+    /// ```
+    ///
+    /// [`bytes::Buf`]: https://docs.rs/bytes/1/bytes/trait.Buf.html
+    /// [`Buf::remaining`]: https://docs.rs/bytes/1/bytes/trait.Buf.html#tymethod.remaining
+    /// [`LEB128DecodeError::BufferOverflow`]: enum.LEB128DecodeError.html#variant.BufferOverflow
+    #[cfg(feature = "bytes_ext")]
+    pub fn read_from_bytes<B: ::bytes::Buf>(buf: B) -> Result<(Self, usize), LEB128DecodeError> {
+        <Self as LEB128>::read_from_bytes(buf)
+    }
+
+    /// Attempts to write a value into an implementor of [`bytes::BufMut`]
+    /// using signed LEB128 compression.
+    ///
+    /// **Note**: Requires the feature `bytes_ext`.
+    ///
+    /// On success this will return the number of bytes that were written.
+    ///
+    /// [`bytes::BufMut`]: https://docs.rs/bytes/1/bytes/trait.BufMut.html
+    #[cfg(feature = "bytes_ext")]
+    pub fn write_into_bytes<B: ::bytes::BufMut>(self, buf: B) -> Result<usize, LEB128EncodeError> {
+        <Self as LEB128>::write_into_bytes(self, buf)
+    }
+
+    /// Compresses the value using signed LEB128 compression, returning the
+    /// encoded bytes inline.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::SLEB128;
+    ///
+    /// let encoded = SLEB128::from(-123456).to_encoded();
+    ///
+    /// assert_eq!(&*encoded, &[0xC0, 0xBB, 0x78]);
+    /// ```
+    pub fn to_encoded(self) -> EncodedSLEB128 {
+        let mut buf = [0; 10];
+        let len = self.write_into(&mut buf).expect("buffer is max width");
+
+        EncodedSLEB128 {
+            buf,
+            len: len as u8,
+        }
+    }
+
+    /// Returns an iterator over the 7-bit groups of the signed LEB128
+    /// encoding of the value, lowest group first.
+    ///
+    /// Each group is the [`payload`] of the corresponding encoded byte, i.e.
+    /// the output of [`bytes`] without continuation bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::SLEB128;
+    ///
+    /// let groups: Vec<u8> = SLEB128::from(-123456).groups().collect();
+    ///
+    /// assert_eq!(groups, [0x40, 0x3B, 0x78]);
+    /// ```
+    ///
+    /// [`payload`]: fn.payload.html
+    /// [`bytes`]: #method.bytes
+    pub fn groups(self) -> impl Iterator<Item = u8> {
+        self.bytes().map(payload)
+    }
+
+    /// Returns an iterator which lazily reads consecutive signed LEB128
+    /// compressed values from a buffer.
+    ///
+    /// The iterator ends cleanly at the end of the buffer. A truncated value
+    /// at the end of the buffer yields [`LEB128DecodeError::BufferOverflow`],
+    /// and the iterator ends after yielding any error.
+    /// [`SLEB128Iter::bytes_consumed`] reports where the valid data ended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::{LEB128DecodeError, SLEB128};
+    ///
+    /// let mut iter = SLEB128::iter_from(&[0x02, 0xE5, 0x8E, 0x26, 0x80]);
+    ///
+    /// assert_eq!(iter.next(), Some(Ok(2)));
+    /// assert_eq!(iter.next(), Some(Ok(624485)));
+    /// assert_eq!(iter.next(), Some(Err(LEB128DecodeError::BufferOverflow)));
+    /// assert_eq!(iter.next(), None);
+    ///
+    /// assert_eq!(iter.bytes_consumed(), 4);
+    /// ```
+    ///
+    /// [`LEB128DecodeError::BufferOverflow`]: enum.LEB128DecodeError.html#variant.BufferOverflow
+    /// [`SLEB128Iter::bytes_consumed`]: struct.SLEB128Iter.html#method.bytes_consumed
+    pub fn iter_from(buf: &[u8]) -> SLEB128Iter<'_> {
+        SLEB128Iter {
+            buf,
+            consumed: 0,
+            failed: false,
+        }
+    }
+
+    /// Returns an iterator over the signed LEB128 encoding of the value.
+    ///
+    /// Each byte is computed on demand, so no buffer is required. The
+    /// iterator yields exactly [`encoded_len`] bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::SLEB128;
+    ///
+    /// let mut buf = vec![0xFF];
+    /// buf.extend(SLEB128::from(-123456).bytes());
+    ///
+    /// assert_eq!(buf, [0xFF, 0xC0, 0xBB, 0x78]);
+    /// ```
+    ///
+    /// [`encoded_len`]: #method.encoded_len
+    pub fn bytes(self) -> SLEB128Bytes {
+        let Self(value) = self;
+
+        SLEB128Bytes {
+            value,
+            remaining: self.encoded_len(),
+        }
+    }
+
+    /// Appends the signed LEB128 compression of the value to a vector.
+    ///
+    /// **Note**: Requires the feature `std`.
+    ///
+    /// Space for the whole value is reserved up front, so the vector grows
+    /// at most once. Returns the number of bytes that were appended, which
+    /// is always equal to [`encoded_len`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::SLEB128;
+    ///
+    /// let mut buf = vec![0xFF];
+    ///
+    /// assert_eq!(SLEB128::from(-123456).append_to_vec(&mut buf), 3);
+    /// assert_eq!(buf, [0xFF, 0xC0, 0xBB, 0x78]);
+    /// ```
+    ///
+    /// [`encoded_len`]: #method.encoded_len
+    #[cfg(feature = "std")]
+    pub fn append_to_vec(self, v: &mut ::std::vec::Vec<u8>) -> usize {
+        let len = self.encoded_len();
+        v.reserve(len);
+        v.extend(self.bytes());
+
+        len
+    }
+
+    /// Returns the signed LEB128 encoding of the value as a string of
+    /// lowercase hexadecimal digits.
+    ///
+    /// **Note**: Requires the feature `std`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::SLEB128;
+    ///
+    /// assert_eq!(SLEB128::from(-123456).to_hex_string(), "c0bb78");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_hex_string(self) -> ::std::string::String {
+        use ::std::fmt::Write;
+
+        let mut s = ::std::string::String::with_capacity(20);
+
+        for byte in self.to_encoded().iter() {
+            let _ = write!(s, "{:02x}", byte);
+        }
+
+        s
+    }
+}
+
+/// The signed LEB128 encoding of a value, stored inline.
+///
+/// Created by [`SLEB128::to_encoded`].
+///
+/// [`SLEB128::to_encoded`]: struct.SLEB128.html#method.to_encoded
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct EncodedSLEB128 {
+    buf: [u8; 10],
+    len: u8,
+}
+
+impl AsRef<[u8]> for EncodedSLEB128 {
+    fn as_ref(&self) -> &[u8] {
+        &self.buf[..usize::from(self.len)]
+    }
+}
+
+impl ops::Deref for EncodedSLEB128 {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_ref()
+    }
+}
+
+/// An iterator over the signed LEB128 encoding of a value.
+///
+/// Created by [`SLEB128::bytes`].
+///
+/// [`SLEB128::bytes`]: struct.SLEB128.html#method.bytes
+#[derive(Debug, Clone)]
+pub struct SLEB128Bytes {
+    value: i64,
+    remaining: usize,
+}
+
+impl Iterator for SLEB128Bytes {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let mut byte = (self.value as u8) & !LEB128_HIGH_ORDER_BIT;
+        self.value >>= 7;
+        self.remaining -= 1;
+
+        if self.remaining != 0 {
+            byte |= LEB128_HIGH_ORDER_BIT;
+        }
+
+        Some(byte)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for SLEB128Bytes {}
+
+impl iter::FusedIterator for SLEB128Bytes {}
+
+/// An iterator which reads consecutive signed LEB128 compressed values from
+/// a buffer.
+///
+/// Created by [`SLEB128::iter_from`].
+///
+/// [`SLEB128::iter_from`]: struct.SLEB128.html#method.iter_from
+#[derive(Debug, Clone)]
+pub struct SLEB128Iter<'a> {
+    buf: &'a [u8],
+    consumed: usize,
+    failed: bool,
+}
+
+impl SLEB128Iter<'_> {
+    /// Returns the number of bytes of the values that have been read
+    /// successfully.
+    pub fn bytes_consumed(&self) -> usize {
+        self.consumed
+    }
+}
+
+impl Iterator for SLEB128Iter<'_> {
+    type Item = Result<i64, LEB128DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.consumed == self.buf.len() {
+            return None;
+        }
+
+        match SLEB128::read_from(&self.buf[self.consumed..]) {
+            Ok((SLEB128(value), len)) => {
+                self.consumed += len;
+                Some(Ok(value))
+            }
+            Err(err) => {
+                self.failed = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+impl iter::FusedIterator for SLEB128Iter<'_> {}
+
+/// A value that can be (de)serialized using _unsigned_ LEB128 variable length
+/// compression.
+///
+/// # Examples
+///
+/// Deserializing a value that was serialized using unsigned LEB128 variable
+/// length compression:
+///
+/// ```
+/// use nano_leb128::ULEB128;
+///
+/// let buf = [0xE5, 0x8E, 0x26];
+///
+/// let (val, len) = ULEB128::read_from(&buf).unwrap();
+///
+/// assert_eq!(u64::from(val), 624485);
+/// assert_eq!(len, 3);
+/// ```
+///
+/// Serializing a value using unsigned LEB128 variable length compression:
+///
+/// ```
+/// use nano_leb128::ULEB128;
+///
+/// let mut buf = [0; 3];
+///
+/// assert_eq!(ULEB128::from(624485).write_into(&mut buf).unwrap(), 3);
+/// assert_eq!(buf, [0xE5, 0x8E, 0x26]);
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ULEB128(u64);
+
+impl From<ULEB128> for u64 {
+    fn from(uleb128: ULEB128) -> Self {
+        uleb128.0
+    }
+}
+
+impl From<u64> for ULEB128 {
+    fn from(val: u64) -> Self {
+        Self(val)
+    }
+}
+
+impl ULEB128 {
+    /// Attempts to read an unsigned LEB128 compressed value from a buffer.
+    ///
+    /// On success this will return the decompressed value and the number of
+    /// bytes that were read.
+    pub fn read_from(buf: &[u8]) -> Result<(Self, usize), LEB128DecodeError> {
+        // Values of a single byte are common enough to skip the general loop.
+        if let Some(&byte) = buf.first() {
+            if byte & LEB128_HIGH_ORDER_BIT == 0 {
+                return Ok((Self(u64::from(byte)), 1));
+            }
+        }
+
+        <Self as LEB128>::read_from(buf)
+    }
+
+    /// Attempts to read an unsigned LEB128 compressed value from a buffer,
+    /// rejecting encodings which are not the shortest possible.
+    ///
+    /// Encodings padded with redundant bytes, such as `[0x80, 0x00]` for
+    /// zero, are accepted by [`read_from`] but are non-canonical under e.g.
+    /// the DWARF and WebAssembly specifications. For these this returns
+    /// [`LEB128DecodeError::Overlong`].
+    ///
+    /// On success this will return the decompressed value and the number of
+    /// bytes that were read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::{LEB128DecodeError, ULEB128};
+    ///
+    /// assert!(ULEB128::read_from_canonical(&[0x00]).is_ok());
+    ///
+    /// assert_eq!(
+    ///     ULEB128::read_from_canonical(&[0x80, 0x00]),
+    ///     Err(LEB128DecodeError::Overlong),
+    /// );
+    /// ```
+    ///
+    /// [`read_from`]: #method.read_from
+    /// [`LEB128DecodeError::Overlong`]: enum.LEB128DecodeError.html#variant.Overlong
+    pub fn read_from_canonical(buf: &[u8]) -> Result<(Self, usize), LEB128DecodeError> {
+        let (value, len) = Self::read_from(buf)?;
+
+        if len > value.encoded_len() {
+            return Err(LEB128DecodeError::Overlong);
+        }
+
+        Ok((value, len))
+    }
+
+    /// Attempts to read exactly `K` consecutive unsigned LEB128 compressed
+    /// values from a buffer.
+    ///
+    /// On success this will return the decompressed values and the total
+    /// number of bytes that were read. Any bytes following the last value
+    /// are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::ULEB128;
+    ///
+    /// let (values, len) = ULEB128::read_array_from::<2>(&[0x01, 0xE5, 0x8E, 0x26]).unwrap();
+    ///
+    /// assert_eq!(values, [1, 624485]);
+    /// assert_eq!(len, 4);
+    /// ```
+    pub fn read_array_from<const K: usize>(
+        buf: &[u8],
+    ) -> Result<([u64; K], usize), LEB128DecodeError> {
+        let mut values = [0; K];
+        let mut len = 0;
+
+        for value in values.iter_mut() {
+            let (Self(decoded), n) = Self::read_from(&buf[len..])?;
+            *value = decoded;
+            len += n;
+        }
+
+        Ok((values, len))
+    }
+
+    /// Reads every unsigned LEB128 compressed value in a buffer, calling `f`
+    /// with each in turn.
+    ///
+    /// On success this will return the total number of bytes that were read,
+    /// which is always `buf.len()`. If the buffer ends with a truncated value
+    /// then [`LEB128DecodeError::BufferOverflow`] is returned, after `f` has
+    /// been called with every complete value before it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::ULEB128;
+    ///
+    /// let mut sum = 0;
+    /// let len = ULEB128::for_each_in(&[0x02, 0xE5, 0x8E, 0x26], |value| sum += value).unwrap();
+    ///
+    /// assert_eq!(sum, 624487);
+    /// assert_eq!(len, 4);
+    /// ```
+    ///
+    /// [`LEB128DecodeError::BufferOverflow`]: enum.LEB128DecodeError.html#variant.BufferOverflow
+    pub fn for_each_in<F: FnMut(u64)>(buf: &[u8], mut f: F) -> Result<usize, LEB128DecodeError> {
+        let mut len = 0;
+
+        while len < buf.len() {
+            let (Self(value), n) = Self::read_from(&buf[len..])?;
+            f(value);
+            len += n;
+        }
+
+        Ok(len)
+    }
+
+    /// Returns `true` if `buf` contains exactly one complete unsigned LEB128
+    /// compressed value, with no trailing bytes.
+    ///
+    /// The value must not overflow a `u64`. As with [`read_from`], over-long
+    /// encodings which pad the value with redundant bytes are valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::ULEB128;
+    ///
+    /// assert!(ULEB128::is_valid_encoding(&[0x00]));
+    /// assert!(ULEB128::is_valid_encoding(&[0x80, 0x00]));
+    ///
+    /// assert!(!ULEB128::is_valid_encoding(&[]));
+    /// assert!(!ULEB128::is_valid_encoding(&[0x80]));
+    /// assert!(!ULEB128::is_valid_encoding(&[0x00, 0x00]));
+    /// ```
+    ///
+    /// [`read_from`]: #method.read_from
+    pub fn is_valid_encoding(buf: &[u8]) -> bool {
+        match Self::read_from(buf) {
+            Ok((_, len)) => len == buf.len(),
+            Err(_) => false,
+        }
+    }
+
+    /// Attempts to read an unsigned LEB128 compressed value from a buffer,
+    /// treating an empty buffer as an encoded zero.
+    ///
+    /// This is useful for formats in which an absent field means zero. An
+    /// empty buffer returns a value of zero and a length of `0`, while a
+    /// non-empty buffer is decoded as by [`read_from`], so a truncated value
+    /// is still reported as [`LEB128DecodeError::BufferOverflow`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::ULEB128;
+    ///
+    /// let (val, len) = ULEB128::read_from_or_default(&[]).unwrap();
+    /// assert_eq!((u64::from(val), len), (0, 0));
+    ///
+    /// assert!(ULEB128::read_from_or_default(&[0x80]).is_err());
+    /// ```
+    ///
+    /// [`read_from`]: #method.read_from
+    /// [`LEB128DecodeError::BufferOverflow`]: enum.LEB128DecodeError.html#variant.BufferOverflow
+    pub fn read_from_or_default(buf: &[u8]) -> Result<(Self, usize), LEB128DecodeError> {
+        if buf.is_empty() {
+            Ok((Self(0), 0))
+        } else {
+            Self::read_from(buf)
+        }
+    }
+
+    /// Attempts to read an unsigned LEB128 compressed value from a buffer,
+    /// reporting the number of bytes that were read even on failure.
+    ///
+    /// On failure the error is returned along with the number of bytes that
+    /// were examined: the whole buffer for
+    /// [`LEB128DecodeError::BufferOverflow`], or up to and including the byte
+    /// which overflowed for [`LEB128DecodeError::IntegerOverflow`]. This
+    /// allows a streaming decoder to skip past a malformed value.
+    ///
+    /// [`LEB128DecodeError::BufferOverflow`]: enum.LEB128DecodeError.html#variant.BufferOverflow
+    /// [`LEB128DecodeError::IntegerOverflow`]: enum.LEB128DecodeError.html#variant.IntegerOverflow
+    pub fn read_from_reporting(buf: &[u8]) -> Result<(Self, usize), (LEB128DecodeError, usize)> {
+        <Self as LEB128>::read_from_reporting(buf)
+    }
+
+    /// Reads an unsigned LEB128 compressed value from a buffer, clamping
+    /// values which do not fit into a `u64` to `u64::MAX`.
+    ///
+    /// Unlike [`read_from`] an over-large value does not produce an error.
+    /// The whole compressed value is still consumed, so the number of bytes
+    /// that were read may be used to continue with any following values. An
+    /// error is only returned if the buffer ends before the value does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::ULEB128;
+    ///
+    /// let buf = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F];
+    ///
+    /// let (val, len) = ULEB128::read_from_saturating(&buf).unwrap();
+    ///
+    /// assert_eq!(u64::from(val), u64::MAX);
+    /// assert_eq!(len, 10);
+    /// ```
+    ///
+    /// [`read_from`]: #method.read_from
+    pub fn read_from_saturating(buf: &[u8]) -> Result<(Self, usize), LEB128DecodeError> {
+        let (value, overflow, len) = uleb128_decode_lossy(buf)?;
+
+        Ok((Self(if overflow { u64::MAX } else { value }), len))
+    }
+
+    /// Reads an unsigned LEB128 compressed value from a buffer, keeping only
+    /// the low 64 bits of values which do not fit into a `u64`.
+    ///
+    /// As with [`read_from_saturating`] the whole compressed value is
+    /// consumed, and an error is only returned if the buffer ends before the
+    /// value does.
+    ///
+    /// [`read_from_saturating`]: #method.read_from_saturating
+    pub fn read_from_wrapping(buf: &[u8]) -> Result<(Self, usize), LEB128DecodeError> {
+        let (value, _, len) = uleb128_decode_lossy(buf)?;
+
+        Ok((Self(value), len))
+    }
+
+    /// Attempts to read an unsigned LEB128 compressed value from a buffer
+    /// into the primitive integer type `T`.
+    ///
+    /// On success this will return the decompressed value and the number of
+    /// bytes that were read. If the value does not fit into `T` then
+    /// [`LEB128DecodeError::IntegerOverflow`] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::{LEB128DecodeError, ULEB128};
+    ///
+    /// let buf = [0xE5, 0x8E, 0x26];
+    ///
+    /// assert_eq!(ULEB128::read_as::<u32>(&buf), Ok((624485, 3)));
+    /// assert_eq!(
+    ///     ULEB128::read_as::<u16>(&buf),
+    ///     Err(LEB128DecodeError::IntegerOverflow),
+    /// );
+    /// ```
+    ///
+    /// [`LEB128DecodeError::IntegerOverflow`]: enum.LEB128DecodeError.html#variant.IntegerOverflow
+    pub fn read_as<T: TryFromUleb>(buf: &[u8]) -> Result<(T, usize), LEB128DecodeError> {
+        let (Self(value), len) = Self::read_from(buf)?;
+        let value = T::try_from_uleb(value).ok_or(LEB128DecodeError::IntegerOverflow)?;
+
+        Ok((value, len))
+    }
+
+    /// Attempts to read a tag-length-value record from a buffer.
+    ///
+    /// The record is an unsigned LEB128 compressed tag, followed by an
+    /// unsigned LEB128 compressed length, followed by that many bytes of
+    /// payload. On success this will return the tag, the payload, and the
+    /// total number of bytes that were read.
+    ///
+    /// If the buffer is shorter than the declared length then
+    /// [`LEB128DecodeError::BufferOverflow`] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::ULEB128;
+    ///
+    /// let buf = [0x2A, 0x03, b'a', b'b', b'c', 0xFF];
+    ///
+    /// let (tag, payload, len) = ULEB128::read_tlv(&buf).unwrap();
+    ///
+    /// assert_eq!(tag, 42);
+    /// assert_eq!(payload, b"abc");
+    /// assert_eq!(len, 5);
+    /// ```
+    ///
+    /// [`LEB128DecodeError::BufferOverflow`]: enum.LEB128DecodeError.html#variant.BufferOverflow
+    pub fn read_tlv(buf: &[u8]) -> Result<(u64, &[u8], usize), LEB128DecodeError> {
+        let (Self(tag), tag_len) = Self::read_from(buf)?;
+        let (Self(data_len), len_len) = Self::read_from(&buf[tag_len..])?;
+        let data_len = usize::try_from(data_len).map_err(|_| LEB128DecodeError::BufferOverflow)?;
+
+        let start = tag_len + len_len;
+        let data = buf
+            .get(start..)
+            .and_then(|rest| rest.get(..data_len))
+            .ok_or(LEB128DecodeError::BufferOverflow)?;
+
+        Ok((tag, data, start + data_len))
+    }
+
+    /// Attempts to write a tag-length-value record into a buffer.
+    ///
+    /// See [`read_tlv`] for the layout of the record. On success this will
+    /// return the number of bytes that were written.
+    ///
+    /// [`read_tlv`]: #method.read_tlv
+    pub fn write_tlv(tag: u64, data: &[u8], buf: &mut [u8]) -> Result<usize, LEB128EncodeError> {
+        let (tag, data_len) = (Self(tag), Self(data.len() as u64));
+        let start = tag.encoded_len() + data_len.encoded_len();
+        let end = start + data.len();
+
+        if buf.len() < end {
+            return Err(LEB128EncodeError::BufferOverflow { needed: end });
+        }
+
+        let tag_len = tag.write_into(buf)?;
+        data_len.write_into(&mut buf[tag_len..])?;
+        buf[start..end].copy_from_slice(data);
+
+        Ok(end)
+    }
+
+    /// Reads an unsigned LEB128 compressed value from a buffer in a const
+    /// context.
+    ///
+    /// This is intended for decoding trusted values at compile time, for
+    /// example when building lookup tables. Any bytes following the value are
+    /// ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer ends before the value is complete, or if the
+    /// value overflows a `u64`. In a const context this surfaces as a compile
+    /// error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::ULEB128;
+    ///
+    /// const VALUE: u64 = ULEB128::read_from_const(&[0xE5, 0x8E, 0x26]);
+    ///
+    /// assert_eq!(VALUE, 624485);
+    /// ```
+    pub const fn read_from_const(buf: &[u8]) -> u64 {
+        let mut result = 0;
+        let mut shift = 0;
+        let mut i = 0;
+
+        loop {
+            if i == buf.len() {
+                panic!("more bytes required than are available");
+            }
+
+            let byte = buf[i];
+
+            if shift == 63 && byte > 1 {
+                panic!("compressed value overflows a u64");
+            }
+
+            result |= ((byte & !LEB128_HIGH_ORDER_BIT) as u64) << shift;
+
+            if byte & LEB128_HIGH_ORDER_BIT == 0 {
+                return result;
+            }
+
+            shift += 7;
+            i += 1;
+        }
+    }
+
+    /// Returns the single byte unsigned LEB128 compression of `value`, or
+    /// `None` if `value` requires more than one byte (i.e. `value >= 0x80`).
+    ///
+    /// As this is `const` it may be used to build lookup tables at compile
+    /// time for fast paths which only ever encode small values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::ULEB128;
+    ///
+    /// const TABLE: [u8; 4] = {
+    ///     let mut table = [0; 4];
+    ///     let mut i = 0;
+    ///
+    ///     while i < table.len() {
+    ///         table[i] = match ULEB128::encode_small(i as u8 * 0x20) {
+    ///             Some(byte) => byte,
+    ///             None => panic!("value requires more than one byte"),
+    ///         };
+    ///         i += 1;
+    ///     }
+    ///
+    ///     table
+    /// };
+    ///
+    /// assert_eq!(TABLE, [0x00, 0x20, 0x40, 0x60]);
+    /// assert_eq!(ULEB128::encode_small(0x80), None);
+    /// ```
+    pub const fn encode_small(value: u8) -> Option<u8> {
+        if value & LEB128_HIGH_ORDER_BIT == 0 {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Attempts to write a value into a buffer using unsigned LEB128
+    /// compression.
+    ///
+    /// On success this will return the number of bytes that were written.
+    pub fn write_into(self, buf: &mut [u8]) -> Result<usize, LEB128EncodeError> {
+        // Values of a single byte are common enough to skip the general loop.
+        if self.0 < u64::from(LEB128_HIGH_ORDER_BIT) {
+            let byte = buf
+                .first_mut()
+                .ok_or(LEB128EncodeError::BufferOverflow { needed: 1 })?;
+            *byte = self.0 as u8;
+
+            return Ok(1);
+        }
+
+        <Self as LEB128>::write_into(self, buf)
+    }
+
+    /// Writes the value into a buffer using unsigned LEB128 compression,
+    /// guaranteeing the shortest possible encoding.
+    ///
+    /// The encoding produced by [`write_into`] is always the shortest
+    /// possible, so this behaves identically. It exists to name the guarantee
+    /// for callers that rely on it, and checks it in debug builds.
+    ///
+    /// On success this will return the number of bytes that were written,
+    /// which is always equal to [`encoded_len`].
+    ///
+    /// [`write_into`]: #method.write_into
+    /// [`encoded_len`]: #method.encoded_len
+    pub fn write_into_canonical(self, buf: &mut [u8]) -> Result<usize, LEB128EncodeError> {
+        let len = self.write_into(buf)?;
+        debug_assert_eq!(len, self.encoded_len());
+
+        Ok(len)
+    }
+
+    /// Writes the value into a buffer using unsigned LEB128 compression,
+    /// padded to exactly `pad_to` bytes.
+    ///
+    /// The value is padded with redundant continuation bytes, which is legal
+    /// but not the shortest possible encoding. This allows a fixed number of
+    /// bytes to be reserved for a value which is patched in later, as done
+    /// by linkers. Padded values of up to 10 bytes are decoded by
+    /// [`read_from`], but are rejected by [`read_from_canonical`].
+    ///
+    /// If the value needs more than `pad_to` bytes, or the buffer is shorter
+    /// than `pad_to` bytes, then [`LEB128EncodeError::BufferOverflow`] is
+    /// returned and the buffer is left untouched. If `pad_to` is more than
+    /// 10 bytes then [`LEB128EncodeError::Overlong`] is returned, as the
+    /// padded value could not be decoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::ULEB128;
+    ///
+    /// let mut buf = [0; 3];
+    ///
+    /// assert_eq!(ULEB128::from(5).write_into_padded(&mut buf, 3).unwrap(), 3);
+    /// assert_eq!(buf, [0x85, 0x80, 0x00]);
+    /// ```
+    ///
+    /// [`read_from`]: #method.read_from
+    /// [`read_from_canonical`]: #method.read_from_canonical
+    /// [`LEB128EncodeError::BufferOverflow`]: enum.LEB128EncodeError.html#variant.BufferOverflow
+    /// [`LEB128EncodeError::Overlong`]: enum.LEB128EncodeError.html#variant.Overlong
+    pub fn write_into_padded(
+        self,
+        buf: &mut [u8],
+        pad_to: usize,
+    ) -> Result<usize, LEB128EncodeError> {
+        if pad_to > 10 {
+            return Err(LEB128EncodeError::Overlong);
+        }
+
+        let needed = self.encoded_len();
+
+        if needed > pad_to {
+            return Err(LEB128EncodeError::BufferOverflow { needed });
+        }
+
+        let buf = buf
+            .get_mut(..pad_to)
+            .ok_or(LEB128EncodeError::BufferOverflow { needed: pad_to })?;
+        let Self(mut value) = self;
+
+        for (i, byte) in buf.iter_mut().enumerate() {
+            *byte = (value as u8) & !LEB128_HIGH_ORDER_BIT;
+            value >>= 7;
+
+            if i + 1 < pad_to {
+                *byte |= LEB128_HIGH_ORDER_BIT;
+            }
+        }
+
+        Ok(pad_to)
+    }
+
+    /// Converts a duration to a whole number of `unit`s.
+    ///
+    /// Any remainder smaller than `unit` is truncated, and durations which
+    /// exceed `u64::MAX` units saturate to `u64::MAX`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use nano_leb128::{DurationUnit, ULEB128};
+    ///
+    /// let value = ULEB128::from_duration(Duration::from_millis(1500), DurationUnit::Micros);
+    ///
+    /// assert_eq!(u64::from(value), 1_500_000);
+    /// assert_eq!(value.to_duration(DurationUnit::Micros), Duration::from_millis(1500));
+    /// ```
+    pub fn from_duration(d: Duration, unit: DurationUnit) -> Self {
+        let count = match unit {
+            DurationUnit::Secs => u128::from(d.as_secs()),
+            DurationUnit::Millis => d.as_millis(),
+            DurationUnit::Micros => d.as_micros(),
+            DurationUnit::Nanos => d.as_nanos(),
+        };
+
+        Self(u64::try_from(count).unwrap_or(u64::MAX))
+    }
+
+    /// Converts the value to a duration of that many `unit`s.
+    ///
+    /// This is the inverse of [`from_duration`].
+    ///
+    /// [`from_duration`]: #method.from_duration
+    pub fn to_duration(self, unit: DurationUnit) -> Duration {
+        let Self(count) = self;
+
+        match unit {
+            DurationUnit::Secs => Duration::from_secs(count),
+            DurationUnit::Millis => Duration::from_millis(count),
+            DurationUnit::Micros => Duration::from_micros(count),
+            DurationUnit::Nanos => Duration::from_nanos(count),
+        }
+    }
+
+    /// Reinterprets the bits of the value as a two's complement signed
+    /// integer.
+    ///
+    /// This is a bit-preserving reinterpretation (`u64 as i64`), not a numeric
+    /// conversion: values above `i64::MAX` become negative. Note that the
+    /// compressed encodings of the two values generally differ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::ULEB128;
+    ///
+    /// assert_eq!(i64::from(ULEB128::from(u64::MAX).reinterpret_signed()), -1);
+    /// assert_eq!(i64::from(ULEB128::from(5).reinterpret_signed()), 5);
+    /// ```
+    pub const fn reinterpret_signed(self) -> SLEB128 {
+        SLEB128(self.0 as i64)
+    }
+
+    /// Returns the number of bytes in the unsigned LEB128 compression of the
+    /// value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::ULEB128;
+    ///
+    /// assert_eq!(ULEB128::from(0).encoded_len(), 1);
+    /// assert_eq!(ULEB128::from(128).encoded_len(), 2);
+    /// assert_eq!(ULEB128::from(624485).encoded_len(), 3);
+    /// assert_eq!(ULEB128::from(u64::MAX).encoded_len(), 10);
+    /// ```
+    pub const fn encoded_len(self) -> usize {
+        let Self(value) = self;
+
+        if value == 0 {
+            1
+        } else {
+            (64 - value.leading_zeros()).div_ceil(7) as usize
+        }
+    }
+
+    /// Attempts to read an unsigned LEB128 compressed value from a source
+    /// function which yields one byte per call.
+    ///
+    /// The source should return `None` once no more bytes are available.
+    ///
+    /// On success this will return the decompressed value and the number of
+    /// bytes that were read.
+    pub fn decode_from_fn<F: FnMut() -> Option<u8>>(
+        next: F,
+    ) -> Result<(Self, usize), LEB128DecodeError> {
+        <Self as LEB128>::decode_from_fn(next)
+    }
+
+    /// Attempts to write a value into a sink function using unsigned LEB128
+    /// compression.
+    ///
+    /// The sink is called once per byte, and should return `false` if the
+    /// byte could not be accepted.
+    ///
+    /// On success this will return the number of bytes that were written.
+    pub fn encode_to_fn<G: FnMut(u8) -> bool>(self, push: G) -> Result<usize, LEB128EncodeError> {
+        <Self as LEB128>::encode_to_fn(self, push)
+    }
+
+    /// Attempts to read an unsigned LEB128 compressed value from an
+    /// implementor of [`std::io::Read`].
+    ///
+    /// **Note**: Requires the feature `std_io_ext`.
+    ///
+    /// On success this will return the decompressed value and the number of
+    /// bytes that were read.
+    ///
+    /// [`std::io::Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
+    #[cfg(feature = "std_io_ext")]
+    pub fn read_from_std_io<R: ::std::io::Read>(reader: R) -> ::std::io::Result<(Self, usize)> {
+        <Self as LEB128>::read_from_std_io(reader)
+    }
+
+    /// Attempts to read an unsigned LEB128 compressed value from an
+    /// implementor of [`std::io::Read`], using `scratch` as the intermediate
+    /// buffer.
+    ///
+    /// **Note**: Requires the feature `std_io_ext`.
+    ///
+    /// This behaves exactly like [`read_from_std_io`], but allows a single
+    /// buffer to be reused across many calls. `scratch` is cleared on entry,
+    /// and holds the bytes that were read on return.
+    ///
+    /// On success this will return the decompressed value and the number of
+    /// bytes that were read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::ULEB128;
+    ///
+    /// let mut reader = &[0x01, 0x02][..];
+    /// let mut scratch = Vec::new();
+    ///
+    /// let (a, _) = ULEB128::read_from_std_io_with(&mut reader, &mut scratch).unwrap();
+    /// let (b, _) = ULEB128::read_from_std_io_with(&mut reader, &mut scratch).unwrap();
+    ///
+    /// assert_eq!((u64::from(a), u64::from(b)), (1, 2));
+    /// ```
+    ///
+    /// [`std::io::Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
+    /// [`read_from_std_io`]: #method.read_from_std_io
+    #[cfg(feature = "std_io_ext")]
+    pub fn read_from_std_io_with<R: ::std::io::Read>(
+        reader: R,
+        scratch: &mut ::std::vec::Vec<u8>,
+    ) -> ::std::io::Result<(Self, usize)> {
+        <Self as LEB128>::read_from_std_io_with(reader, scratch)
+    }
+
+    /// Attempts to read an unsigned LEB128 compressed value from an implementor
+    /// of [`std::io::Read`] without allocating.
+    ///
+    /// **Note**: Requires the feature `std_io_ext`.
+    ///
+    /// Unlike [`read_from_std_io`] no intermediate buffer is allocated. At
+    /// most 10 bytes are read, the maximum width of a compressed `u64`.
+    ///
+    /// On success this will return the decompressed value and the number of
+    /// bytes that were read.
+    ///
+    /// [`std::io::Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
+    /// [`read_from_std_io`]: #method.read_from_std_io
+    #[cfg(feature = "std_io_ext")]
+    pub fn read_from_std_io_no_alloc<R: ::std::io::Read>(
+        reader: R,
+    ) -> ::std::io::Result<(Self, usize)> {
+        <Self as LEB128>::read_from_std_io_no_alloc(reader)
+    }
+
+    /// Attempts to read an unsigned LEB128 compressed value from an implementor
+    /// of [`std::io::BufRead`].
+    ///
+    /// **Note**: Requires the feature `std_io_ext`.
+    ///
+    /// Unlike [`read_from_std_io`] this scans the reader's internal buffer
+    /// rather than issuing a read per byte, which avoids a syscall per byte
+    /// for buffered sockets and files.
+    ///
+    /// On success this will return the decompressed value and the number of
+    /// bytes that were read.
+    ///
+    /// [`std::io::BufRead`]: https://doc.rust-lang.org/std/io/trait.BufRead.html
+    /// [`read_from_std_io`]: #method.read_from_std_io
+    #[cfg(feature = "std_io_ext")]
+    pub fn read_from_buf_read<R: ::std::io::BufRead>(
+        reader: R,
+    ) -> ::std::io::Result<(Self, usize)> {
+        <Self as LEB128>::read_from_buf_read(reader)
+    }
+
+    /// Attempts to write a value into an implementor of [`std::io::Write`]
+    /// using unsigned LEB128 compression.
+    ///
+    /// **Note**: Requires the feature `std_io_ext`.
+    ///
+    /// On success this will return the number of bytes that were written.
+    ///
+    /// [`std::io::Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
+    #[cfg(feature = "std_io_ext")]
+    pub fn write_into_std_io<W: ::std::io::Write>(self, writer: W) -> ::std::io::Result<usize> {
+        <Self as LEB128>::write_into_std_io(self, writer)
+    }
+
+    /// Attempts to read an unsigned LEB128 compressed value from an
+    /// implementor of [`byteio::ReadBytes`].
+    ///
+    /// **Note**: Requires the feature `byteio_ext`.
+    ///
+    /// On success this will return the decompressed value and the number of
+    /// bytes that were read.
+    ///
+    /// [`byteio::ReadBytes`]: https://docs.rs/byteio/latest/trait.ReadBytes.html
+    #[cfg(feature = "byteio_ext")]
+    pub fn read_from_byteio<'a, R: ReadBytes<'a>>(
+        reader: R,
+    ) -> Result<(Self, usize), LEB128DecodeError> {
+        <Self as LEB128>::read_from_byteio(reader)
+    }
+
+    /// Attempts to read an unsigned LEB128 compressed value from an
+    /// implementor of [`byteio::ReadBytes`], returning the advanced reader.
+    ///
+    /// **Note**: Requires the feature `byteio_ext`.
+    ///
+    /// This allows several values to be decoded in turn from a reader which
+    /// is passed by value, e.g. a byte slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::{ULEB128, LEB128DecodeError};
+    ///
+    /// fn read_pair(buf: &[u8]) -> Result<(u64, u64), LEB128DecodeError> {
+    ///     let (a, rest) = ULEB128::read_from_byteio_keep(buf)?;
+    ///     let (b, _) = ULEB128::read_from_byteio_keep(rest)?;
+    ///
+    ///     Ok((u64::from(a), u64::from(b)))
+    /// }
+    ///
+    /// assert_eq!(read_pair(&[0x01, 0x02]), Ok((1, 2)));
+    /// ```
+    ///
+    /// [`byteio::ReadBytes`]: https://docs.rs/byteio/latest/trait.ReadBytes.html
+    #[cfg(feature = "byteio_ext")]
+    pub fn read_from_byteio_keep<'a, R: ReadBytes<'a>>(
+        reader: R,
+    ) -> Result<(Self, R), LEB128DecodeError> {
+        let mut reader = ::byteio::Reader::new(reader);
+        let value = Self::leb128_decode(&mut reader)?;
+
+        Ok((value, reader.into_inner()))
+    }
+
+    /// Attempts to write a value into an implementor of [`byteio::WriteBytes`]
+    /// using unsigned LEB128 compression.
+    ///
+    /// **Note**: Requires the feature `byteio_ext`.
+    ///
+    /// On success this will return the number of bytes that were written.
+    ///
+    /// [`byteio::WriteBytes`]: https://docs.rs/byteio/latest/trait.WriteBytes.html
+    #[cfg(feature = "byteio_ext")]
+    pub fn write_into_byteio<W: WriteBytes>(self, writer: W) -> Result<usize, LEB128EncodeError> {
+        <Self as LEB128>::write_into_byteio(self, writer)
+    }
+
+    /// Attempts to read an unsigned LEB128 compressed value from an
+    /// implementor of [`bytes::Buf`].
+    ///
+    /// **Note**: Requires the feature `bytes_ext`.
+    ///
+    /// On success this will return the decompressed value and the number of
+    /// bytes that were read. Reading never goes past [`Buf::remaining`]; if
+    /// the value is truncated then [`LEB128DecodeError::BufferOverflow`] is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use nano_leb128::ULEB128;
+    ///
+    /// let mut buf = Bytes::from_static(&[0x02, 0xE5, 0x8E, 0x26]);
+    ///
+    /// let (a, _) = ULEB128::read_from_bytes(&mut buf).unwrap();
+    /// let (b, _) = ULEB128::read_from_bytes(&mut buf).unwrap();
+    ///
+    /// assert_eq!((u64::from(a), u64::from(b)), (2, 624485));
+    /// ```
+    ///
+    /// [`bytes::Buf`]: https://docs.rs/bytes/1/bytes/trait.Buf.html
+    /// [`Buf::remaining`]: https://docs.rs/bytes/1/bytes/trait.Buf.html#tymethod.remaining
+    /// [`LEB128DecodeError::BufferOverflow`]: enum.LEB128DecodeError.html#variant.BufferOverflow
+    #[cfg(feature = "bytes_ext")]
+    pub fn read_from_bytes<B: ::bytes::Buf>(buf: B) -> Result<(Self, usize), LEB128DecodeError> {
+        <Self as LEB128>::read_from_bytes(buf)
+    }
+
+    /// Attempts to write a value into an implementor of [`bytes::BufMut`]
+    /// using unsigned LEB128 compression.
+    ///
+    /// **Note**: Requires the feature `bytes_ext`.
+    ///
+    /// On success this will return the number of bytes that were written.
+    ///
+    /// [`bytes::BufMut`]: https://docs.rs/bytes/1/bytes/trait.BufMut.html
+    #[cfg(feature = "bytes_ext")]
+    pub fn write_into_bytes<B: ::bytes::BufMut>(self, buf: B) -> Result<usize, LEB128EncodeError> {
+        <Self as LEB128>::write_into_bytes(self, buf)
+    }
+
+    /// Compresses the value using unsigned LEB128 compression, returning the
+    /// encoded bytes inline.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::ULEB128;
+    ///
+    /// let encoded = ULEB128::from(624485).to_encoded();
+    ///
+    /// assert_eq!(&*encoded, &[0xE5, 0x8E, 0x26]);
+    /// ```
+    pub fn to_encoded(self) -> EncodedULEB128 {
+        let mut buf = [0; 10];
+        let len = self.write_into(&mut buf).expect("buffer is max width");
+
+        EncodedULEB128 {
+            buf,
+            len: len as u8,
+        }
+    }
+
+    /// Returns an iterator over the 7-bit groups of the unsigned LEB128
+    /// encoding of the value, lowest group first.
+    ///
+    /// Each group is the [`payload`] of the corresponding encoded byte, i.e.
+    /// the output of [`bytes`] without continuation bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::ULEB128;
+    ///
+    /// let groups: Vec<u8> = ULEB128::from(624485).groups().collect();
+    ///
+    /// assert_eq!(groups, [0x65, 0x0E, 0x26]);
+    /// ```
+    ///
+    /// [`payload`]: fn.payload.html
+    /// [`bytes`]: #method.bytes
+    pub fn groups(self) -> impl Iterator<Item = u8> {
+        self.bytes().map(payload)
+    }
+
+    /// Returns an iterator which lazily reads consecutive unsigned LEB128
+    /// compressed values from a buffer.
+    ///
+    /// The iterator ends cleanly at the end of the buffer. A truncated value
+    /// at the end of the buffer yields [`LEB128DecodeError::BufferOverflow`],
+    /// and the iterator ends after yielding any error.
+    /// [`ULEB128Iter::bytes_consumed`] reports where the valid data ended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::{LEB128DecodeError, ULEB128};
+    ///
+    /// let mut iter = ULEB128::iter_from(&[0x02, 0xE5, 0x8E, 0x26, 0x80]);
+    ///
+    /// assert_eq!(iter.next(), Some(Ok(2)));
+    /// assert_eq!(iter.next(), Some(Ok(624485)));
+    /// assert_eq!(iter.next(), Some(Err(LEB128DecodeError::BufferOverflow)));
+    /// assert_eq!(iter.next(), None);
+    ///
+    /// assert_eq!(iter.bytes_consumed(), 4);
+    /// ```
+    ///
+    /// [`LEB128DecodeError::BufferOverflow`]: enum.LEB128DecodeError.html#variant.BufferOverflow
+    /// [`ULEB128Iter::bytes_consumed`]: struct.ULEB128Iter.html#method.bytes_consumed
+    pub fn iter_from(buf: &[u8]) -> ULEB128Iter<'_> {
+        ULEB128Iter {
+            buf,
+            consumed: 0,
+            failed: false,
+        }
+    }
+
+    /// Returns an iterator over the unsigned LEB128 encoding of the value.
+    ///
+    /// Each byte is computed on demand, so no buffer is required. The
+    /// iterator yields exactly [`encoded_len`] bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::ULEB128;
+    ///
+    /// let mut buf = vec![0xFF];
+    /// buf.extend(ULEB128::from(624485).bytes());
+    ///
+    /// assert_eq!(buf, [0xFF, 0xE5, 0x8E, 0x26]);
+    /// ```
+    ///
+    /// [`encoded_len`]: #method.encoded_len
+    pub fn bytes(self) -> ULEB128Bytes {
+        let Self(value) = self;
+
+        ULEB128Bytes {
+            value,
+            remaining: self.encoded_len(),
+        }
+    }
+
+    /// Appends the unsigned LEB128 compression of the value to a vector.
+    ///
+    /// **Note**: Requires the feature `std`.
+    ///
+    /// Space for the whole value is reserved up front, so the vector grows
+    /// at most once. Returns the number of bytes that were appended, which
+    /// is always equal to [`encoded_len`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::ULEB128;
+    ///
+    /// let mut buf = vec![0xFF];
+    ///
+    /// assert_eq!(ULEB128::from(624485).append_to_vec(&mut buf), 3);
+    /// assert_eq!(buf, [0xFF, 0xE5, 0x8E, 0x26]);
+    /// ```
+    ///
+    /// [`encoded_len`]: #method.encoded_len
+    #[cfg(feature = "std")]
+    pub fn append_to_vec(self, v: &mut ::std::vec::Vec<u8>) -> usize {
+        let len = self.encoded_len();
+        v.reserve(len);
+        v.extend(self.bytes());
+
+        len
+    }
+
+    /// Returns the unsigned LEB128 encoding of the value as a string of
+    /// lowercase hexadecimal digits.
+    ///
+    /// **Note**: Requires the feature `std`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::ULEB128;
+    ///
+    /// assert_eq!(ULEB128::from(624485).to_hex_string(), "e58e26");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_hex_string(self) -> ::std::string::String {
+        use ::std::fmt::Write;
+
+        let mut s = ::std::string::String::with_capacity(20);
+
+        for byte in self.to_encoded().iter() {
+            let _ = write!(s, "{:02x}", byte);
+        }
+
+        s
+    }
+}
+
+/// The unsigned LEB128 encoding of a value, stored inline.
+///
+/// Created by [`ULEB128::to_encoded`].
+///
+/// [`ULEB128::to_encoded`]: struct.ULEB128.html#method.to_encoded
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct EncodedULEB128 {
+    buf: [u8; 10],
+    len: u8,
+}
+
+impl AsRef<[u8]> for EncodedULEB128 {
+    fn as_ref(&self) -> &[u8] {
+        &self.buf[..usize::from(self.len)]
+    }
+}
+
+impl ops::Deref for EncodedULEB128 {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_ref()
+    }
+}
+
+/// An iterator over the unsigned LEB128 encoding of a value.
+///
+/// Created by [`ULEB128::bytes`].
+///
+/// [`ULEB128::bytes`]: struct.ULEB128.html#method.bytes
+#[derive(Debug, Clone)]
+pub struct ULEB128Bytes {
+    value: u64,
+    remaining: usize,
+}
+
+impl Iterator for ULEB128Bytes {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let mut byte = (self.value as u8) & !LEB128_HIGH_ORDER_BIT;
+        self.value >>= 7;
+        self.remaining -= 1;
+
+        if self.remaining != 0 {
+            byte |= LEB128_HIGH_ORDER_BIT;
+        }
+
+        Some(byte)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for ULEB128Bytes {}
+
+impl iter::FusedIterator for ULEB128Bytes {}
+
+/// An iterator which reads consecutive unsigned LEB128 compressed values from
+/// a buffer.
+///
+/// Created by [`ULEB128::iter_from`].
+///
+/// [`ULEB128::iter_from`]: struct.ULEB128.html#method.iter_from
+#[derive(Debug, Clone)]
+pub struct ULEB128Iter<'a> {
+    buf: &'a [u8],
+    consumed: usize,
+    failed: bool,
+}
+
+impl ULEB128Iter<'_> {
+    /// Returns the number of bytes of the values that have been read
+    /// successfully.
+    pub fn bytes_consumed(&self) -> usize {
+        self.consumed
+    }
+}
+
+impl Iterator for ULEB128Iter<'_> {
+    type Item = Result<u64, LEB128DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.consumed == self.buf.len() {
+            return None;
+        }
+
+        match ULEB128::read_from(&self.buf[self.consumed..]) {
+            Ok((ULEB128(value), len)) => {
+                self.consumed += len;
+                Some(Ok(value))
+            }
+            Err(err) => {
+                self.failed = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+impl iter::FusedIterator for ULEB128Iter<'_> {}
+
+/// A 128-bit value that can be (de)serialized using _signed_ LEB128 variable
+/// length compression.
+///
+/// This is the 128-bit counterpart of [`SLEB128`]. A compressed `i128` takes
+/// up to 19 bytes.
+///
+/// # Examples
+///
+/// ```
+/// use nano_leb128::SLEB128_128;
+///
+/// let mut buf = [0; 19];
+///
+/// let len = SLEB128_128::from(i128::MIN).write_into(&mut buf).unwrap();
+/// assert_eq!(len, 19);
+///
+/// let (val, len) = SLEB128_128::read_from(&buf).unwrap();
+/// assert_eq!(i128::from(val), i128::MIN);
+/// assert_eq!(len, 19);
+/// ```
+///
+/// [`SLEB128`]: struct.SLEB128.html
+#[allow(non_camel_case_types)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct SLEB128_128(i128);
+
+impl From<SLEB128_128> for i128 {
+    fn from(sleb128: SLEB128_128) -> Self {
+        sleb128.0
+    }
+}
+
+impl From<i128> for SLEB128_128 {
+    fn from(val: i128) -> Self {
+        Self(val)
+    }
+}
+
+impl From<SLEB128> for SLEB128_128 {
+    fn from(sleb128: SLEB128) -> Self {
+        Self(i128::from(sleb128.0))
+    }
+}
+
+impl SLEB128_128 {
+    /// Attempts to read a signed LEB128 compressed value from a buffer.
+    ///
+    /// On success this will return the decompressed value and the number of
+    /// bytes that were read.
+    pub fn read_from(buf: &[u8]) -> Result<(Self, usize), LEB128DecodeError> {
+        <Self as LEB128>::read_from(buf)
+    }
+
+    /// Attempts to write a value into a buffer using signed LEB128
+    /// compression.
+    ///
+    /// On success this will return the number of bytes that were written.
+    pub fn write_into(self, buf: &mut [u8]) -> Result<usize, LEB128EncodeError> {
+        <Self as LEB128>::write_into(self, buf)
+    }
+
+    /// Returns the number of bytes in the signed LEB128 compression of the
+    /// value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_leb128::SLEB128_128;
+    ///
+    /// assert_eq!(SLEB128_128::from(-64).encoded_len(), 1);
+    /// assert_eq!(SLEB128_128::from(i128::MAX).encoded_len(), 19);
+    /// ```
+    pub const fn encoded_len(self) -> usize {
+        let Self(value) = self;
+
+        // The number of significant bits, including the sign bit.
+        let bits = if value < 0 {
+            129 - (!value).leading_zeros()
+        } else {
+            129 - value.leading_zeros()
+        };
+
+        bits.div_ceil(7) as usize
+    }
+}
+
+/// A 128-bit value that can be (de)serialized using _unsigned_ LEB128
+/// variable length compression.
+///
+/// This is the 128-bit counterpart of [`ULEB128`]. A compressed `u128` takes
+/// up to 19 bytes.
+///
+/// # Examples
+///
+/// ```
+/// use nano_leb128::ULEB128_128;
+///
+/// let mut buf = [0; 19];
+///
+/// let len = ULEB128_128::from(u128::MAX).write_into(&mut buf).unwrap();
+/// assert_eq!(len, 19);
+///
+/// let (val, len) = ULEB128_128::read_from(&buf).unwrap();
+/// assert_eq!(u128::from(val), u128::MAX);
+/// assert_eq!(len, 19);
+/// ```
+///
+/// [`ULEB128`]: struct.ULEB128.html
+#[allow(non_camel_case_types)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ULEB128_128(u128);
+
+impl From<ULEB128_128> for u128 {
+    fn from(uleb128: ULEB128_128) -> Self {
         uleb128.0
     }
 }
 
-impl From<u64> for ULEB128 {
-    fn from(val: u64) -> Self {
+impl From<u128> for ULEB128_128 {
+    fn from(val: u128) -> Self {
         Self(val)
     }
 }
 
-impl ULEB128 {
+impl From<ULEB128> for ULEB128_128 {
+    fn from(uleb128: ULEB128) -> Self {
+        Self(u128::from(uleb128.0))
+    }
+}
+
+impl ULEB128_128 {
     /// Attempts to read an unsigned LEB128 compressed value from a buffer.
     ///
     /// On success this will return the decompressed value and the number of
@@ -262,61 +2378,309 @@ impl ULEB128 {
         <Self as LEB128>::write_into(self, buf)
     }
 
-    /// Attempts to read an unsigned LEB128 compressed value from an
-    /// implementor of [`std::io::Read`].
+    /// Returns the number of bytes in the unsigned LEB128 compression of the
+    /// value.
     ///
-    /// **Note**: Requires the feature `std_io_ext`.
+    /// # Examples
     ///
-    /// On success this will return the decompressed value and the number of
-    /// bytes that were read.
+    /// ```
+    /// use nano_leb128::ULEB128_128;
     ///
-    /// [`std::io::Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
-    #[cfg(feature = "std_io_ext")]
-    pub fn read_from_std_io<R: ::std::io::Read>(reader: R) -> ::std::io::Result<(Self, usize)> {
-        <Self as LEB128>::read_from_std_io(reader)
+    /// assert_eq!(ULEB128_128::from(127).encoded_len(), 1);
+    /// assert_eq!(ULEB128_128::from(u128::MAX).encoded_len(), 19);
+    /// ```
+    pub const fn encoded_len(self) -> usize {
+        let Self(value) = self;
+
+        if value == 0 {
+            1
+        } else {
+            (128 - value.leading_zeros()).div_ceil(7) as usize
+        }
     }
+}
 
-    /// Attempts to write a value into an implementor of [`std::io::Write`]
-    /// using unsigned LEB128 compression.
+/// Sequentially writes LEB128 compressed values into a buffer.
+///
+/// # Examples
+///
+/// ```
+/// use nano_leb128::{LEB128Encoder, SLEB128, ULEB128};
+///
+/// let mut buf = [0; 4];
+/// let mut encoder = LEB128Encoder::new(&mut buf);
+///
+/// encoder.push(ULEB128::from(624485)).unwrap();
+/// encoder.push_signed(SLEB128::from(-1)).unwrap();
+///
+/// assert_eq!(encoder.num_bytes_written(), 4);
+/// assert_eq!(buf, [0xE5, 0x8E, 0x26, 0x7F]);
+/// ```
+#[derive(Debug)]
+pub struct LEB128Encoder<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+impl<'a> LEB128Encoder<'a> {
+    /// Creates a new encoder which writes into `buf`.
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    /// Attempts to write a value into the remaining buffer using unsigned
+    /// LEB128 compression.
     ///
-    /// **Note**: Requires the feature `std_io_ext`.
+    /// On success this will return the number of bytes that were written.
+    ///
+    /// If the value does not fit then the position of the encoder is
+    /// unchanged, but the bytes after it may have been partially
+    /// overwritten. The `needed` length of the error is that of the whole
+    /// buffer, including the bytes already written.
+    pub fn push(&mut self, value: ULEB128) -> Result<usize, LEB128EncodeError> {
+        let len = value
+            .write_into(&mut self.buf[self.pos..])
+            .map_err(|err| self.overflow(err))?;
+        self.pos += len;
+
+        Ok(len)
+    }
+
+    /// Attempts to write a value into the remaining buffer using signed
+    /// LEB128 compression.
     ///
     /// On success this will return the number of bytes that were written.
     ///
-    /// [`std::io::Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
-    #[cfg(feature = "std_io_ext")]
-    pub fn write_into_std_io<W: ::std::io::Write>(self, writer: W) -> ::std::io::Result<usize> {
-        <Self as LEB128>::write_into_std_io(self, writer)
+    /// If the value does not fit then the position of the encoder is
+    /// unchanged, but the bytes after it may have been partially
+    /// overwritten. The `needed` length of the error is that of the whole
+    /// buffer, including the bytes already written.
+    pub fn push_signed(&mut self, value: SLEB128) -> Result<usize, LEB128EncodeError> {
+        let len = value
+            .write_into(&mut self.buf[self.pos..])
+            .map_err(|err| self.overflow(err))?;
+        self.pos += len;
+
+        Ok(len)
+    }
+
+    /// Returns the total number of bytes written into the buffer.
+    pub fn num_bytes_written(&self) -> usize {
+        self.pos
+    }
+
+    /// Offsets the length needed by a failed write into the remaining buffer
+    /// by the bytes already written.
+    fn overflow(&self, err: LEB128EncodeError) -> LEB128EncodeError {
+        match err {
+            LEB128EncodeError::BufferOverflow { needed } => LEB128EncodeError::BufferOverflow {
+                needed: self.pos + needed,
+            },
+            err => err,
+        }
+    }
+}
+
+/// Writes LEB128 compressed values into an implementor of
+/// [`std::io::Write`], batching them into as few writes as possible.
+///
+/// **Note**: Requires the feature `std_io_ext`.
+///
+/// Each value is compressed exactly as by `write_into_std_io`, but into an
+/// internal buffer which is only written out once full, or when the writer is
+/// flushed or dropped. This avoids issuing a write per value when
+/// serializing long sequences of values to a socket or file.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+///
+/// use nano_leb128::{LEB128Writer, SLEB128, ULEB128};
+///
+/// let mut writer = LEB128Writer::new(Vec::new());
+///
+/// writer.push(ULEB128::from(624485)).unwrap();
+/// writer.push_signed(SLEB128::from(-1)).unwrap();
+/// writer.flush().unwrap();
+///
+/// assert_eq!(writer.get_ref(), &[0xE5, 0x8E, 0x26, 0x7F]);
+/// ```
+///
+/// [`std::io::Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
+#[cfg(feature = "std_io_ext")]
+#[derive(Debug)]
+pub struct LEB128Writer<W: ::std::io::Write> {
+    inner: ::std::io::BufWriter<W>,
+}
+
+#[cfg(feature = "std_io_ext")]
+impl<W: ::std::io::Write> LEB128Writer<W> {
+    /// Creates a new writer with a 4 KiB buffer.
+    pub fn new(writer: W) -> Self {
+        Self::with_capacity(4096, writer)
+    }
+
+    /// Creates a new writer with a buffer of at least `capacity` bytes.
+    pub fn with_capacity(capacity: usize, writer: W) -> Self {
+        Self {
+            inner: ::std::io::BufWriter::with_capacity(capacity, writer),
+        }
+    }
+
+    /// Attempts to write a value using unsigned LEB128 compression.
+    ///
+    /// On success this will return the number of bytes that were buffered.
+    pub fn push(&mut self, value: ULEB128) -> ::std::io::Result<usize> {
+        let encoded = value.to_encoded();
+        ::std::io::Write::write_all(&mut self.inner, &encoded)?;
+
+        Ok(encoded.len())
+    }
+
+    /// Attempts to write a value using signed LEB128 compression.
+    ///
+    /// On success this will return the number of bytes that were buffered.
+    pub fn push_signed(&mut self, value: SLEB128) -> ::std::io::Result<usize> {
+        let encoded = value.to_encoded();
+        ::std::io::Write::write_all(&mut self.inner, &encoded)?;
+
+        Ok(encoded.len())
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner.get_ref()
+    }
+
+    /// Flushes the buffer and unwraps the underlying writer.
+    pub fn into_inner(self) -> ::std::io::Result<W> {
+        self.inner.into_inner().map_err(|err| err.into_error())
+    }
+}
+
+#[cfg(feature = "std_io_ext")]
+impl<W: ::std::io::Write> ::std::io::Write for LEB128Writer<W> {
+    fn write(&mut self, buf: &[u8]) -> ::std::io::Result<usize> {
+        self.inner.write(buf)
     }
 
-    /// Attempts to read an unsigned LEB128 compressed value from an
-    /// implementor of [`byteio::ReadBytes`].
-    ///
-    /// **Note**: Requires the feature `byteio_ext`.
-    ///
-    /// On success this will return the decompressed value and the number of
-    /// bytes that were read.
-    ///
-    /// [`byteio::ReadBytes`]: https://docs.rs/byteio/latest/trait.ReadBytes.html
-    #[cfg(feature = "byteio_ext")]
-    pub fn read_from_byteio<'a, R: ReadBytes<'a>>(
-        reader: R,
-    ) -> Result<(Self, usize), LEB128DecodeError> {
-        <Self as LEB128>::read_from_byteio(reader)
+    fn flush(&mut self) -> ::std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// The bit set in every LEB128 compressed byte except the last.
+pub const LEB128_HIGH_ORDER_BIT: u8 = 1 << 7;
+
+/// The bit set in the last byte of a negative signed LEB128 compressed value.
+pub const LEB128_SIGN_BIT: u8 = 1 << 6;
+
+/// Returns `true` if more bytes follow `byte` in a LEB128 compressed value.
+///
+/// # Examples
+///
+/// ```
+/// use nano_leb128::is_continuation;
+///
+/// assert!(is_continuation(0xE5));
+/// assert!(!is_continuation(0x26));
+/// ```
+pub const fn is_continuation(byte: u8) -> bool {
+    byte & LEB128_HIGH_ORDER_BIT != 0
+}
+
+/// Returns the seven bits of the value carried by a LEB128 compressed byte.
+///
+/// # Examples
+///
+/// ```
+/// use nano_leb128::payload;
+///
+/// assert_eq!(payload(0xE5), 0x65);
+/// assert_eq!(payload(0x26), 0x26);
+/// ```
+pub const fn payload(byte: u8) -> u8 {
+    byte & !LEB128_HIGH_ORDER_BIT
+}
+
+/// Returns `true` if the sign bit of a signed LEB128 compressed byte is set.
+///
+/// This is only meaningful for the last byte of a value, where it determines
+/// whether the decoded value is negative.
+///
+/// # Examples
+///
+/// ```
+/// use nano_leb128::sign_bit_set;
+///
+/// // -123456 is compressed as [0xC0, 0xBB, 0x78].
+/// assert!(sign_bit_set(0x78));
+///
+/// // 624485 is compressed as [0xE5, 0x8E, 0x26].
+/// assert!(!sign_bit_set(0x26));
+/// ```
+pub const fn sign_bit_set(byte: u8) -> bool {
+    byte & LEB128_SIGN_BIT != 0
+}
+
+/// Attempts to read a LEB128 compressed value from a buffer, with the
+/// signedness of the value chosen at runtime.
+///
+/// This reads an [`SLEB128`] if `signed` is `true` and a [`ULEB128`]
+/// otherwise. The decompressed value is widened to `i128`, which losslessly
+/// holds both.
+///
+/// On success this will return the decompressed value and the number of bytes
+/// that were read.
+///
+/// # Examples
+///
+/// ```
+/// use nano_leb128::read_dynamic;
+///
+/// assert_eq!(read_dynamic(&[0x7F], true).unwrap(), (-1, 1));
+/// assert_eq!(read_dynamic(&[0x7F], false).unwrap(), (127, 1));
+/// ```
+///
+/// [`SLEB128`]: struct.SLEB128.html
+/// [`ULEB128`]: struct.ULEB128.html
+pub fn read_dynamic(buf: &[u8], signed: bool) -> Result<(i128, usize), LEB128DecodeError> {
+    if signed {
+        SLEB128::read_from(buf).map(|(value, len)| (i128::from(i64::from(value)), len))
+    } else {
+        ULEB128::read_from(buf).map(|(value, len)| (i128::from(u64::from(value)), len))
     }
+}
 
-    /// Attempts to write a value into an implementor of [`byteio::WriteBytes`]
-    /// using unsigned LEB128 compression.
-    ///
-    /// **Note**: Requires the feature `byteio_ext`.
-    ///
-    /// On success this will return the number of bytes that were written.
-    ///
-    /// [`byteio::WriteBytes`]: https://docs.rs/byteio/latest/trait.WriteBytes.html
-    #[cfg(feature = "byteio_ext")]
-    pub fn write_into_byteio<W: WriteBytes>(self, writer: W) -> Result<usize, LEB128EncodeError> {
-        <Self as LEB128>::write_into_byteio(self, writer)
-    }
+/// A primitive integer type that unsigned LEB128 compressed values can be
+/// decoded into.
+///
+/// This trait is sealed and is implemented for `u8`, `u16`, `u32`, `u64`, and
+/// `u128`. See [`ULEB128::read_as`].
+///
+/// [`ULEB128::read_as`]: struct.ULEB128.html#method.read_as
+pub trait TryFromUleb: sealed::Sealed + Sized {
+    #[doc(hidden)]
+    fn try_from_uleb(value: u64) -> Option<Self>;
+}
+
+/// The unit used when converting between durations and [`ULEB128`] values.
+///
+/// See [`ULEB128::from_duration`].
+///
+/// [`ULEB128`]: struct.ULEB128.html
+/// [`ULEB128::from_duration`]: struct.ULEB128.html#method.from_duration
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DurationUnit {
+    /// Whole seconds.
+    Secs,
+    /// Milliseconds.
+    Millis,
+    /// Microseconds.
+    Micros,
+    /// Nanoseconds.
+    Nanos,
 }
 
 /// Errors that can occur when decoding LEB128 compressed values.
@@ -333,6 +2697,10 @@ pub enum LEB128DecodeError {
     BufferOverflow,
     /// The compressed value represents a larger number than can be decoded.
     IntegerOverflow,
+    /// The compressed value is padded with redundant bytes.
+    ///
+    /// This is only returned by the `read_from_canonical` methods.
+    Overlong,
 }
 
 #[cfg(feature = "std")]
@@ -340,7 +2708,9 @@ impl From<LEB128DecodeError> for ::std::io::Error {
     fn from(err: LEB128DecodeError) -> Self {
         match err {
             LEB128DecodeError::BufferOverflow => ::std::io::ErrorKind::UnexpectedEof.into(),
-            LEB128DecodeError::IntegerOverflow => ::std::io::ErrorKind::InvalidData.into(),
+            LEB128DecodeError::IntegerOverflow | LEB128DecodeError::Overlong => {
+                ::std::io::ErrorKind::InvalidData.into()
+            }
         }
     }
 }
@@ -356,14 +2726,26 @@ impl From<LEB128DecodeError> for ::std::io::Error {
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum LEB128EncodeError {
     /// More bytes required than are available to complete the serialization.
-    BufferOverflow,
+    BufferOverflow {
+        /// The total number of bytes required by the serialization.
+        ///
+        /// A buffer of at least this length will not overflow when the
+        /// serialization is retried.
+        needed: usize,
+    },
+    /// The requested padding is wider than the longest encoding which can
+    /// be decoded.
+    ///
+    /// This is only returned by the `write_into_padded` methods.
+    Overlong,
 }
 
 #[cfg(feature = "std")]
 impl From<LEB128EncodeError> for ::std::io::Error {
     fn from(err: LEB128EncodeError) -> Self {
         match err {
-            LEB128EncodeError::BufferOverflow => ::std::io::ErrorKind::UnexpectedEof.into(),
+            LEB128EncodeError::BufferOverflow { .. } => ::std::io::ErrorKind::UnexpectedEof.into(),
+            LEB128EncodeError::Overlong => ::std::io::ErrorKind::InvalidInput.into(),
         }
     }
 }
@@ -374,18 +2756,135 @@ impl From<LEB128EncodeError> for ::std::io::Error {
  *
  */
 
-const LEB128_HIGH_ORDER_BIT: u8 = 1 << 7;
-const LEB128_SIGN_BIT: u8 = 1 << 6;
-
-trait LEB128Decode: Sized {
-    fn leb128_decode<'a, R: ReadBytes<'a>>(reader: R) -> Result<Self, LEB128DecodeError>;
+/// A type that can be deserialized from a LEB128 compressed value.
+///
+/// This is implemented for [`SLEB128`] and [`ULEB128`], and can be
+/// implemented for domain specific newtypes to decode them directly, most
+/// simply by forwarding to one of those types.
+///
+/// # Invariants
+///
+/// Implementations of [`leb128_decode_fn`] must:
+///
+/// * Read bytes up to and including the first byte without the
+///   continuation (high order) bit set, and no further.
+/// * Return [`LEB128DecodeError::BufferOverflow`] if the source runs out of
+///   bytes before the value is complete. Readers which receive bytes
+///   incrementally rely on this to know that they should retry with more
+///   bytes.
+/// * Return [`LEB128DecodeError::IntegerOverflow`] if the value does not fit
+///   into `Self`.
+///
+/// # Examples
+///
+/// ```
+/// use nano_leb128::{LEB128Decode, LEB128DecodeError, ULEB128};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct PageCount(u64);
+///
+/// impl LEB128Decode for PageCount {
+///     fn leb128_decode_fn<F: FnMut() -> Option<u8>>(
+///         next: F,
+///     ) -> Result<Self, LEB128DecodeError> {
+///         ULEB128::leb128_decode_fn(next).map(|pages| PageCount(u64::from(pages)))
+///     }
+/// }
+///
+/// assert_eq!(PageCount::leb128_decode(&[0xE5, 0x8E, 0x26][..]), Ok(PageCount(624485)));
+/// ```
+///
+/// [`SLEB128`]: struct.SLEB128.html
+/// [`ULEB128`]: struct.ULEB128.html
+/// [`leb128_decode_fn`]: #tymethod.leb128_decode_fn
+/// [`LEB128DecodeError::BufferOverflow`]: enum.LEB128DecodeError.html#variant.BufferOverflow
+/// [`LEB128DecodeError::IntegerOverflow`]: enum.LEB128DecodeError.html#variant.IntegerOverflow
+pub trait LEB128Decode: Sized {
+    /// Attempts to decode a value from a source function which yields one
+    /// byte per call, returning `None` once no more bytes are available.
+    fn leb128_decode_fn<F: FnMut() -> Option<u8>>(next: F) -> Result<Self, LEB128DecodeError>;
+
+    /// Attempts to decode a value from a reader.
+    fn leb128_decode<'a, R: ReadBytes<'a>>(mut reader: R) -> Result<Self, LEB128DecodeError> {
+        Self::leb128_decode_fn(|| reader.try_read_u8().ok())
+    }
 }
 
-trait LEB128Encode {
-    fn leb128_encode<W: WriteBytes>(self, writer: W) -> Result<(), LEB128EncodeError>;
+/// A type that can be serialized as a LEB128 compressed value.
+///
+/// This is implemented for [`SLEB128`] and [`ULEB128`], and can be
+/// implemented for domain specific newtypes to encode them directly, most
+/// simply by forwarding to one of those types.
+///
+/// # Invariants
+///
+/// Implementations of [`leb128_encode_fn`] must:
+///
+/// * Push the minimal encoding of the value, least significant group first,
+///   with the continuation (high order) bit set on every byte except the
+///   last.
+/// * Stop and return [`LEB128EncodeError::BufferOverflow`] as soon as the
+///   sink rejects a byte.
+///
+/// # Examples
+///
+/// ```
+/// use nano_leb128::{LEB128Encode, LEB128EncodeError, ULEB128};
+///
+/// struct PageCount(u64);
+///
+/// impl LEB128Encode for PageCount {
+///     fn leb128_encode_fn<G: FnMut(u8) -> bool>(self, push: G) -> Result<(), LEB128EncodeError> {
+///         ULEB128::from(self.0).leb128_encode_fn(push)
+///     }
+/// }
+///
+/// let mut buf = [0; 3];
+/// PageCount(624485).leb128_encode(&mut buf[..]).unwrap();
+///
+/// assert_eq!(buf, [0xE5, 0x8E, 0x26]);
+/// ```
+///
+/// [`SLEB128`]: struct.SLEB128.html
+/// [`ULEB128`]: struct.ULEB128.html
+/// [`leb128_encode_fn`]: #tymethod.leb128_encode_fn
+/// [`LEB128EncodeError::BufferOverflow`]: enum.LEB128EncodeError.html#variant.BufferOverflow
+pub trait LEB128Encode: Sized {
+    /// Attempts to encode the value into a sink function which accepts one
+    /// byte per call, returning `false` if the byte could not be accepted.
+    fn leb128_encode_fn<G: FnMut(u8) -> bool>(self, push: G) -> Result<(), LEB128EncodeError>;
+
+    /// Attempts to encode the value into a writer.
+    fn leb128_encode<W: WriteBytes>(self, mut writer: W) -> Result<(), LEB128EncodeError> {
+        self.leb128_encode_fn(|byte| writer.try_write_u8(byte).is_ok())
+    }
 }
 
 trait LEB128: LEB128Decode + LEB128Encode {
+    fn decode_from_fn<F: FnMut() -> Option<u8>>(
+        mut next: F,
+    ) -> Result<(Self, usize), LEB128DecodeError> {
+        let mut len = 0;
+        let value = Self::leb128_decode_fn(|| {
+            let byte = next()?;
+            len += 1;
+            Some(byte)
+        })?;
+
+        Ok((value, len))
+    }
+
+    fn encode_to_fn<G: FnMut(u8) -> bool>(self, mut push: G) -> Result<usize, LEB128EncodeError> {
+        let mut len = 0;
+        self.leb128_encode_fn(|byte| {
+            let pushed = push(byte);
+            len += pushed as usize;
+            pushed
+        })?;
+
+        Ok(len)
+    }
+
     fn read_from(buf: &[u8]) -> Result<(Self, usize), LEB128DecodeError> {
         let mut reader = ::byteio::Reader::new(buf);
         let value = Self::leb128_decode(&mut reader)?;
@@ -393,6 +2892,21 @@ trait LEB128: LEB128Decode + LEB128Encode {
         Ok((value, reader.num_bytes_read()))
     }
 
+    fn read_from_reporting(buf: &[u8]) -> Result<(Self, usize), (LEB128DecodeError, usize)> {
+        let mut len = 0;
+
+        let result = Self::leb128_decode_fn(|| {
+            let byte = *buf.get(len)?;
+            len += 1;
+            Some(byte)
+        });
+
+        match result {
+            Ok(value) => Ok((value, len)),
+            Err(err) => Err((err, len)),
+        }
+    }
+
     fn write_into(self, buf: &mut [u8]) -> Result<usize, LEB128EncodeError> {
         let mut writer = ::byteio::Writer::new(buf);
         self.leb128_encode(&mut writer)?;
@@ -401,15 +2915,23 @@ trait LEB128: LEB128Decode + LEB128Encode {
     }
 
     #[cfg(feature = "std_io_ext")]
-    fn read_from_std_io<R: ::std::io::Read>(mut reader: R) -> ::std::io::Result<(Self, usize)> {
-        let mut buf = ::std::vec::Vec::with_capacity(10);
+    fn read_from_std_io<R: ::std::io::Read>(reader: R) -> ::std::io::Result<(Self, usize)> {
+        Self::read_from_std_io_with(reader, &mut ::std::vec::Vec::with_capacity(10))
+    }
+
+    #[cfg(feature = "std_io_ext")]
+    fn read_from_std_io_with<R: ::std::io::Read>(
+        mut reader: R,
+        buf: &mut ::std::vec::Vec<u8>,
+    ) -> ::std::io::Result<(Self, usize)> {
+        buf.clear();
 
         loop {
             let mut byte = [0];
             reader.read_exact(&mut byte)?;
             buf.push(byte[0]);
 
-            match Self::leb128_decode(&*buf) {
+            match Self::leb128_decode(&buf[..]) {
                 Ok(val) => {
                     return Ok((val, buf.len()));
                 }
@@ -421,6 +2943,72 @@ trait LEB128: LEB128Decode + LEB128Encode {
         }
     }
 
+    #[cfg(feature = "std_io_ext")]
+    fn read_from_std_io_no_alloc<R: ::std::io::Read>(
+        mut reader: R,
+    ) -> ::std::io::Result<(Self, usize)> {
+        let mut io_err = None;
+
+        let result = Self::decode_from_fn(|| {
+            let mut byte = [0];
+
+            match reader.read_exact(&mut byte) {
+                Ok(()) => Some(byte[0]),
+                Err(err) => {
+                    io_err = Some(err);
+                    None
+                }
+            }
+        });
+
+        match (result, io_err) {
+            (Ok(result), _) => Ok(result),
+            (Err(_), Some(err)) => Err(err),
+            (Err(err), None) => Err(err.into()),
+        }
+    }
+
+    #[cfg(feature = "std_io_ext")]
+    fn read_from_buf_read<R: ::std::io::BufRead>(
+        mut reader: R,
+    ) -> ::std::io::Result<(Self, usize)> {
+        let mut buf = ::std::vec::Vec::with_capacity(10);
+
+        loop {
+            let available = reader.fill_buf()?;
+
+            if available.is_empty() {
+                return Err(::std::io::ErrorKind::UnexpectedEof.into());
+            }
+
+            let mut result = None;
+            let mut consumed = 0;
+
+            for &byte in available {
+                buf.push(byte);
+                consumed += 1;
+
+                match Self::leb128_decode(&*buf) {
+                    Ok(val) => {
+                        result = Some(Ok((val, buf.len())));
+                        break;
+                    }
+                    Err(err) if err == LEB128DecodeError::IntegerOverflow => {
+                        result = Some(Err(err.into()));
+                        break;
+                    }
+                    _ => (),
+                }
+            }
+
+            reader.consume(consumed);
+
+            if let Some(result) = result {
+                return result;
+            }
+        }
+    }
+
     #[cfg(feature = "std_io_ext")]
     fn write_into_std_io<W: ::std::io::Write>(self, mut writer: W) -> ::std::io::Result<usize> {
         let mut buf = ::std::vec::Vec::with_capacity(10);
@@ -447,17 +3035,38 @@ trait LEB128: LEB128Decode + LEB128Encode {
 
         Ok(writer.num_bytes_written())
     }
+
+    #[cfg(feature = "bytes_ext")]
+    fn read_from_bytes<B: ::bytes::Buf>(mut buf: B) -> Result<(Self, usize), LEB128DecodeError> {
+        Self::decode_from_fn(|| {
+            if buf.has_remaining() {
+                Some(buf.get_u8())
+            } else {
+                None
+            }
+        })
+    }
+
+    #[cfg(feature = "bytes_ext")]
+    fn write_into_bytes<B: ::bytes::BufMut>(self, mut buf: B) -> Result<usize, LEB128EncodeError> {
+        self.encode_to_fn(|byte| {
+            if buf.has_remaining_mut() {
+                buf.put_u8(byte);
+                true
+            } else {
+                false
+            }
+        })
+    }
 }
 
 impl LEB128Decode for SLEB128 {
-    fn leb128_decode<'a, R: ReadBytes<'a>>(mut reader: R) -> Result<Self, LEB128DecodeError> {
+    fn leb128_decode_fn<F: FnMut() -> Option<u8>>(mut next: F) -> Result<Self, LEB128DecodeError> {
         let mut result = 0;
         let mut shift = 0;
 
         let byte = loop {
-            let byte = reader
-                .try_read_u8()
-                .map_err(|_| LEB128DecodeError::BufferOverflow)?;
+            let byte = next().ok_or(LEB128DecodeError::BufferOverflow)?;
 
             if shift == 63 && byte != 0x00 && byte != !LEB128_HIGH_ORDER_BIT {
                 return Err(LEB128DecodeError::IntegerOverflow);
@@ -480,7 +3089,7 @@ impl LEB128Decode for SLEB128 {
 }
 
 impl LEB128Encode for SLEB128 {
-    fn leb128_encode<W: WriteBytes>(self, mut writer: W) -> Result<(), LEB128EncodeError> {
+    fn leb128_encode_fn<G: FnMut(u8) -> bool>(self, mut push: G) -> Result<(), LEB128EncodeError> {
         let Self(mut value) = self;
         let mut more = true;
 
@@ -496,26 +3105,24 @@ impl LEB128Encode for SLEB128 {
                 byte |= LEB128_HIGH_ORDER_BIT;
             }
 
-            writer
-                .try_write_u8(byte)
-                .map_err(|_| LEB128EncodeError::BufferOverflow)?;
+            if !push(byte) {
+                return Err(LEB128EncodeError::BufferOverflow {
+                    needed: self.encoded_len(),
+                });
+            }
         }
 
         Ok(())
     }
 }
 
-impl LEB128 for SLEB128 {}
-
 impl LEB128Decode for ULEB128 {
-    fn leb128_decode<'a, R: ReadBytes<'a>>(mut reader: R) -> Result<Self, LEB128DecodeError> {
+    fn leb128_decode_fn<F: FnMut() -> Option<u8>>(mut next: F) -> Result<Self, LEB128DecodeError> {
         let mut result = 0;
         let mut shift = 0;
 
         loop {
-            let byte = reader
-                .try_read_u8()
-                .map_err(|_| LEB128DecodeError::BufferOverflow)?;
+            let byte = next().ok_or(LEB128DecodeError::BufferOverflow)?;
 
             if shift == 63 && byte > 1 {
                 return Err(LEB128DecodeError::IntegerOverflow);
@@ -533,7 +3140,114 @@ impl LEB128Decode for ULEB128 {
 }
 
 impl LEB128Encode for ULEB128 {
-    fn leb128_encode<W: WriteBytes>(self, mut writer: W) -> Result<(), LEB128EncodeError> {
+    fn leb128_encode_fn<G: FnMut(u8) -> bool>(self, mut push: G) -> Result<(), LEB128EncodeError> {
+        let Self(mut value) = self;
+
+        loop {
+            let mut byte = (value as u8) & !LEB128_HIGH_ORDER_BIT;
+            value >>= 7;
+
+            if value != 0 {
+                byte |= LEB128_HIGH_ORDER_BIT;
+            }
+
+            if !push(byte) {
+                return Err(LEB128EncodeError::BufferOverflow {
+                    needed: self.encoded_len(),
+                });
+            }
+
+            if value == 0 {
+                return Ok(());
+            }
+        }
+    }
+}
+
+impl LEB128Decode for SLEB128_128 {
+    fn leb128_decode_fn<F: FnMut() -> Option<u8>>(mut next: F) -> Result<Self, LEB128DecodeError> {
+        let mut result = 0;
+        let mut shift = 0;
+
+        let byte = loop {
+            let byte = next().ok_or(LEB128DecodeError::BufferOverflow)?;
+
+            // The last byte carries bits 126 and 127, and the bits above must
+            // extend the sign.
+            if shift == 126 && byte != 0x00 && byte != 0x01 && byte != 0x7E && byte != 0x7F {
+                return Err(LEB128DecodeError::IntegerOverflow);
+            }
+
+            result |= i128::from(byte & !LEB128_HIGH_ORDER_BIT) << shift;
+            shift += 7;
+
+            if byte & LEB128_HIGH_ORDER_BIT == 0 {
+                break byte;
+            }
+        };
+
+        if shift < 8 * mem::size_of::<i128>() && (byte & LEB128_SIGN_BIT) != 0 {
+            result |= !0 << shift;
+        }
+
+        Ok(Self(result))
+    }
+}
+
+impl LEB128Encode for SLEB128_128 {
+    fn leb128_encode_fn<G: FnMut(u8) -> bool>(self, mut push: G) -> Result<(), LEB128EncodeError> {
+        let Self(mut value) = self;
+        let mut more = true;
+
+        while more {
+            let mut byte = (value as u8) & !LEB128_HIGH_ORDER_BIT;
+            value >>= 7;
+
+            if value == 0 && (byte & LEB128_SIGN_BIT) == 0
+                || value == -1 && (byte & LEB128_SIGN_BIT) != 0
+            {
+                more = false;
+            } else {
+                byte |= LEB128_HIGH_ORDER_BIT;
+            }
+
+            if !push(byte) {
+                return Err(LEB128EncodeError::BufferOverflow {
+                    needed: self.encoded_len(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl LEB128Decode for ULEB128_128 {
+    fn leb128_decode_fn<F: FnMut() -> Option<u8>>(mut next: F) -> Result<Self, LEB128DecodeError> {
+        let mut result = 0;
+        let mut shift = 0;
+
+        loop {
+            let byte = next().ok_or(LEB128DecodeError::BufferOverflow)?;
+
+            // The last byte carries bits 126 and 127.
+            if shift == 126 && byte > 3 {
+                return Err(LEB128DecodeError::IntegerOverflow);
+            }
+
+            result |= u128::from(byte & !LEB128_HIGH_ORDER_BIT) << shift;
+
+            if byte & LEB128_HIGH_ORDER_BIT == 0 {
+                return Ok(Self(result));
+            }
+
+            shift += 7;
+        }
+    }
+}
+
+impl LEB128Encode for ULEB128_128 {
+    fn leb128_encode_fn<G: FnMut(u8) -> bool>(self, mut push: G) -> Result<(), LEB128EncodeError> {
         let Self(mut value) = self;
 
         loop {
@@ -544,9 +3258,11 @@ impl LEB128Encode for ULEB128 {
                 byte |= LEB128_HIGH_ORDER_BIT;
             }
 
-            writer
-                .try_write_u8(byte)
-                .map_err(|_| LEB128EncodeError::BufferOverflow)?;
+            if !push(byte) {
+                return Err(LEB128EncodeError::BufferOverflow {
+                    needed: self.encoded_len(),
+                });
+            }
 
             if value == 0 {
                 return Ok(());
@@ -555,4 +3271,92 @@ impl LEB128Encode for ULEB128 {
     }
 }
 
-impl LEB128 for ULEB128 {}
+impl<T: LEB128Decode + LEB128Encode> LEB128 for T {}
+
+/// Decodes a signed LEB128 compressed value of any length, returning the low
+/// 64 bits of the value, the sign of the value if it does not fit into an
+/// `i64` (`Some(true)` if negative), and the number of bytes that were read.
+fn sleb128_decode_lossy(buf: &[u8]) -> Result<(i64, Option<bool>, usize), LEB128DecodeError> {
+    let mut result = 0;
+    let mut shift = 0u32;
+    let mut high_ones = false;
+    let mut high_zeros = false;
+
+    for (i, &byte) in buf.iter().enumerate() {
+        let payload = byte & !LEB128_HIGH_ORDER_BIT;
+
+        if shift < 64 {
+            result |= i64::from(payload) << shift;
+        }
+
+        // Bits from position 63 upwards must all match the sign bit.
+        if shift >= 63 {
+            high_ones |= payload != 0;
+            high_zeros |= payload != !LEB128_HIGH_ORDER_BIT;
+        }
+
+        shift = shift.saturating_add(7);
+
+        if byte & LEB128_HIGH_ORDER_BIT == 0 {
+            let negative = byte & LEB128_SIGN_BIT != 0;
+
+            if shift < 64 && negative {
+                result |= !0 << shift;
+            }
+
+            let overflow = if negative { high_zeros } else { high_ones };
+
+            return Ok((result, if overflow { Some(negative) } else { None }, i + 1));
+        }
+    }
+
+    Err(LEB128DecodeError::BufferOverflow)
+}
+
+/// Decodes an unsigned LEB128 compressed value of any length, returning the
+/// low 64 bits of the value, whether the value overflows a `u64`, and the
+/// number of bytes that were read.
+fn uleb128_decode_lossy(buf: &[u8]) -> Result<(u64, bool, usize), LEB128DecodeError> {
+    let mut result = 0;
+    let mut shift = 0u32;
+    let mut overflow = false;
+
+    for (i, &byte) in buf.iter().enumerate() {
+        let payload = u64::from(byte & !LEB128_HIGH_ORDER_BIT);
+
+        if shift < 64 {
+            result |= payload << shift;
+            overflow |= shift == 63 && payload > 1;
+        } else {
+            overflow |= payload != 0;
+        }
+
+        if byte & LEB128_HIGH_ORDER_BIT == 0 {
+            return Ok((result, overflow, i + 1));
+        }
+
+        shift = shift.saturating_add(7);
+    }
+
+    Err(LEB128DecodeError::BufferOverflow)
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_try_from_uleb {
+    ($($ty:ty),*) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl TryFromUleb for $ty {
+                fn try_from_uleb(value: u64) -> Option<Self> {
+                    <$ty>::try_from(value).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_uleb!(u8, u16, u32, u64, u128);
@@ -1,4 +1,7 @@
-use nano_leb128::{LEB128DecodeError, LEB128EncodeError, SLEB128, ULEB128};
+use nano_leb128::{
+    LEB128Decode, LEB128DecodeError, LEB128Encode, LEB128EncodeError, LEB128Encoder, SLEB128,
+    SLEB128_128, ULEB128, ULEB128_128,
+};
 
 use quickcheck_macros::quickcheck;
 
@@ -22,7 +25,7 @@ fn qc_uleb128(val: u64) -> bool {
     u64::from(result) == val && n0 == n1
 }
 
-#[cfg(feature = "std_io_extra")]
+#[cfg(feature = "std_io_ext")]
 #[quickcheck]
 fn qc_sleb128_std_io(val: i64) -> bool {
     let mut buf = Vec::with_capacity(10);
@@ -36,7 +39,7 @@ fn qc_sleb128_std_io(val: i64) -> bool {
     i64::from(result) == val && n0 == n1
 }
 
-#[cfg(feature = "std_io_extra")]
+#[cfg(feature = "std_io_ext")]
 #[quickcheck]
 fn qc_uleb128_std_io(val: u64) -> bool {
     let mut buf = Vec::with_capacity(10);
@@ -50,34 +53,81 @@ fn qc_uleb128_std_io(val: u64) -> bool {
     u64::from(result) == val && n0 == n1
 }
 
-#[cfg(feature = "byteio_extra")]
+#[cfg(feature = "byteio_ext")]
 #[quickcheck]
 fn qc_sleb128_byteio(val: i64) -> bool {
-    let mut buf = Vec::with_capacity(10);
+    let mut buf = [0; 10];
 
     let n0 = SLEB128::from(val)
-        .write_into_byteio(&mut buf)
+        .write_into_byteio(&mut buf[..])
         .expect("write");
 
-    let (result, n1) = SLEB128::read_from_byteio(&*buf).expect("read");
+    let (result, n1) = SLEB128::read_from_byteio(&buf[..]).expect("read");
 
     i64::from(result) == val && n0 == n1
 }
 
-#[cfg(feature = "byteio_extra")]
+#[cfg(feature = "byteio_ext")]
 #[quickcheck]
 fn qc_uleb128_byteio(val: u64) -> bool {
-    let mut buf = Vec::with_capacity(10);
+    let mut buf = [0; 10];
 
     let n0 = ULEB128::from(val)
-        .write_into_byteio(&mut buf)
+        .write_into_byteio(&mut buf[..])
         .expect("write");
 
-    let (result, n1) = ULEB128::read_from_byteio(&*buf).expect("read");
+    let (result, n1) = ULEB128::read_from_byteio(&buf[..]).expect("read");
 
     u64::from(result) == val && n0 == n1
 }
 
+#[cfg(feature = "bytes_ext")]
+#[quickcheck]
+fn qc_sleb128_bytes_buf(val: i64) -> bool {
+    let mut buf = bytes::BytesMut::new();
+
+    let n0 = SLEB128::from(val)
+        .write_into_bytes(&mut buf)
+        .expect("write");
+
+    let mut buf = buf.freeze();
+    let (result, n1) = SLEB128::read_from_bytes(&mut buf).expect("read");
+
+    i64::from(result) == val && n0 == n1 && buf.is_empty()
+}
+
+#[cfg(feature = "bytes_ext")]
+#[quickcheck]
+fn qc_uleb128_bytes_buf(val: u64) -> bool {
+    let mut buf = bytes::BytesMut::new();
+
+    let n0 = ULEB128::from(val)
+        .write_into_bytes(&mut buf)
+        .expect("write");
+
+    let mut buf = buf.freeze();
+    let (result, n1) = ULEB128::read_from_bytes(&mut buf).expect("read");
+
+    u64::from(result) == val && n0 == n1 && buf.is_empty()
+}
+
+#[cfg(feature = "bytes_ext")]
+#[test]
+fn leb128_bytes_buffer_overflow() {
+    let mut buf = bytes::Bytes::from_static(&[0x80, 0x80]);
+    assert_eq!(
+        ULEB128::read_from_bytes(&mut buf),
+        Err(LEB128DecodeError::BufferOverflow)
+    );
+    assert!(buf.is_empty());
+
+    let mut buf = [0; 2];
+    assert_eq!(
+        SLEB128::from(-123456).write_into_bytes(&mut buf[..]),
+        Err(LEB128EncodeError::BufferOverflow { needed: 3 })
+    );
+}
+
 #[test]
 fn sleb128_decode_buffer_overflow() {
     let buf = [0x80];
@@ -120,22 +170,1260 @@ fn uleb128_decode_integer_overflow() {
 
 #[test]
 fn sleb128_encode_buffer_overflow() {
-    let val = i64::max_value();
+    let val = i64::MAX;
     let mut buf = [0; 9];
 
     assert_eq!(
         SLEB128::from(val).write_into(&mut buf).unwrap_err(),
-        LEB128EncodeError::BufferOverflow
+        LEB128EncodeError::BufferOverflow { needed: 10 }
     );
 }
 
 #[test]
 fn uleb128_encode_buffer_overflow() {
-    let val = u64::max_value();
+    let val = u64::MAX;
     let mut buf = [0; 9];
 
     assert_eq!(
         ULEB128::from(val).write_into(&mut buf).unwrap_err(),
-        LEB128EncodeError::BufferOverflow
+        LEB128EncodeError::BufferOverflow { needed: 10 }
+    );
+}
+
+#[quickcheck]
+fn qc_sleb128_to_encoded(val: i64) -> bool {
+    let mut buf = [0; 10];
+
+    let n = SLEB128::from(val).write_into(&mut buf).expect("write");
+
+    *SLEB128::from(val).to_encoded() == buf[..n]
+}
+
+#[quickcheck]
+fn qc_uleb128_to_encoded(val: u64) -> bool {
+    let mut buf = [0; 10];
+
+    let n = ULEB128::from(val).write_into(&mut buf).expect("write");
+
+    *ULEB128::from(val).to_encoded() == buf[..n]
+}
+
+#[test]
+fn leb128_encoder() {
+    let mut buf = [0; 6];
+    let mut encoder = LEB128Encoder::new(&mut buf);
+
+    assert_eq!(encoder.push(ULEB128::from(624485)).expect("push"), 3);
+    assert_eq!(
+        encoder.push_signed(SLEB128::from(-123456)).expect("push"),
+        3
+    );
+    assert_eq!(
+        encoder.push(ULEB128::from(0)).unwrap_err(),
+        LEB128EncodeError::BufferOverflow { needed: 7 }
+    );
+    assert_eq!(encoder.num_bytes_written(), 6);
+    assert_eq!(buf, [0xE5, 0x8E, 0x26, 0xC0, 0xBB, 0x78]);
+}
+
+#[test]
+fn leb128_encoder_buffer_overflow() {
+    let mut buf = [0; 4];
+    let mut encoder = LEB128Encoder::new(&mut buf);
+
+    assert_eq!(encoder.push(ULEB128::from(1)).expect("push"), 1);
+    assert_eq!(
+        encoder.push(ULEB128::from(624485 << 7)).unwrap_err(),
+        LEB128EncodeError::BufferOverflow { needed: 5 }
+    );
+    assert_eq!(encoder.num_bytes_written(), 1);
+    assert_eq!(encoder.push(ULEB128::from(624485)).expect("push"), 3);
+    assert_eq!(encoder.num_bytes_written(), 4);
+}
+
+#[cfg(feature = "std_io_ext")]
+#[quickcheck]
+fn qc_sleb128_buf_read(val: i64) -> bool {
+    let mut buf = Vec::with_capacity(10);
+
+    let n0 = SLEB128::from(val)
+        .write_into_std_io(&mut buf)
+        .expect("write");
+
+    let reader = std::io::BufReader::with_capacity(3, &*buf);
+    let (result, n1) = SLEB128::read_from_buf_read(reader).expect("read");
+
+    i64::from(result) == val && n0 == n1
+}
+
+#[cfg(feature = "std_io_ext")]
+#[quickcheck]
+fn qc_uleb128_buf_read(val: u64) -> bool {
+    let mut buf = Vec::with_capacity(10);
+
+    let n0 = ULEB128::from(val)
+        .write_into_std_io(&mut buf)
+        .expect("write");
+
+    let reader = std::io::BufReader::with_capacity(3, &*buf);
+    let (result, n1) = ULEB128::read_from_buf_read(reader).expect("read");
+
+    u64::from(result) == val && n0 == n1
+}
+
+#[cfg(feature = "std_io_ext")]
+#[test]
+fn uleb128_buf_read_consumes_value_only() {
+    use std::io::BufRead;
+
+    let buf = [0xE5, 0x8E, 0x26, 0x7F];
+    let mut reader = &buf[..];
+
+    let (val, len) = ULEB128::read_from_buf_read(&mut reader).expect("read");
+
+    assert_eq!(u64::from(val), 624485);
+    assert_eq!(len, 3);
+    assert_eq!(reader.fill_buf().expect("fill"), &[0x7F]);
+}
+
+#[cfg(feature = "std_io_ext")]
+#[test]
+fn uleb128_buf_read_errors() {
+    use std::io::ErrorKind;
+
+    let buf = [0x80, 0x80];
+    let err = ULEB128::read_from_buf_read(&buf[..]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+    let buf = [0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x02];
+    let err = ULEB128::read_from_buf_read(&buf[..]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn sleb128_to_hex_string() {
+    assert_eq!(SLEB128::from(0).to_hex_string(), "00");
+    assert_eq!(SLEB128::from(-1).to_hex_string(), "7f");
+    assert_eq!(SLEB128::from(-123456).to_hex_string(), "c0bb78");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn uleb128_to_hex_string() {
+    assert_eq!(ULEB128::from(0).to_hex_string(), "00");
+    assert_eq!(ULEB128::from(127).to_hex_string(), "7f");
+    assert_eq!(ULEB128::from(624485).to_hex_string(), "e58e26");
+    assert_eq!(
+        ULEB128::from(u64::MAX).to_hex_string(),
+        "ffffffffffffffffff01"
+    );
+}
+
+#[quickcheck]
+fn qc_uleb128_read_from_const(val: u64) -> bool {
+    let mut buf = [0; 10];
+
+    ULEB128::from(val).write_into(&mut buf).expect("write");
+
+    ULEB128::read_from_const(&buf) == val
+}
+
+#[test]
+#[should_panic]
+fn uleb128_read_from_const_buffer_overflow() {
+    ULEB128::read_from_const(&[0x80]);
+}
+
+#[test]
+#[should_panic]
+fn uleb128_read_from_const_integer_overflow() {
+    ULEB128::read_from_const(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x02]);
+}
+
+#[quickcheck]
+fn qc_sleb128_fn(val: i64) -> bool {
+    let mut buf = Vec::with_capacity(10);
+
+    let n0 = SLEB128::from(val)
+        .encode_to_fn(|byte| {
+            buf.push(byte);
+            true
+        })
+        .expect("write");
+
+    let mut bytes = buf.iter().copied();
+    let (result, n1) = SLEB128::decode_from_fn(|| bytes.next()).expect("read");
+
+    i64::from(result) == val && n0 == n1 && n0 == buf.len()
+}
+
+#[quickcheck]
+fn qc_uleb128_fn(val: u64) -> bool {
+    let mut buf = Vec::with_capacity(10);
+
+    let n0 = ULEB128::from(val)
+        .encode_to_fn(|byte| {
+            buf.push(byte);
+            true
+        })
+        .expect("write");
+
+    let mut bytes = buf.iter().copied();
+    let (result, n1) = ULEB128::decode_from_fn(|| bytes.next()).expect("read");
+
+    u64::from(result) == val && n0 == n1 && n0 == buf.len()
+}
+
+#[test]
+fn uleb128_fn_errors() {
+    let mut bytes = [0x80, 0x80].iter().copied();
+    assert_eq!(
+        ULEB128::decode_from_fn(|| bytes.next()).unwrap_err(),
+        LEB128DecodeError::BufferOverflow
+    );
+
+    let mut remaining = 2;
+    let sink = |_| {
+        remaining -= 1;
+        remaining >= 0
+    };
+    assert_eq!(
+        ULEB128::from(624485).encode_to_fn(sink).unwrap_err(),
+        LEB128EncodeError::BufferOverflow { needed: 3 }
+    );
+}
+
+// Known vectors, including those from the DWARF specification. These must
+// hold regardless of host endianness.
+
+#[test]
+fn sleb128_known_vectors() {
+    let cases: &[(i64, &[u8])] = &[
+        (0, &[0x00]),
+        (2, &[0x02]),
+        (-2, &[0x7E]),
+        (63, &[0x3F]),
+        (-64, &[0x40]),
+        (127, &[0xFF, 0x00]),
+        (-127, &[0x81, 0x7F]),
+        (128, &[0x80, 0x01]),
+        (-128, &[0x80, 0x7F]),
+        (129, &[0x81, 0x01]),
+        (-129, &[0xFF, 0x7E]),
+        (-123456, &[0xC0, 0xBB, 0x78]),
+        (
+            i64::MAX,
+            &[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00],
+        ),
+        (
+            i64::MIN,
+            &[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7F],
+        ),
+    ];
+
+    for &(val, encoded) in cases {
+        let mut buf = [0; 10];
+
+        let len = SLEB128::from(val).write_into(&mut buf).expect("write");
+        assert_eq!(&buf[..len], encoded, "{}", val);
+
+        let (result, len) = SLEB128::read_from(encoded).expect("read");
+        assert_eq!(i64::from(result), val);
+        assert_eq!(len, encoded.len());
+    }
+}
+
+#[test]
+fn uleb128_known_vectors() {
+    let cases: &[(u64, &[u8])] = &[
+        (0, &[0x00]),
+        (2, &[0x02]),
+        (127, &[0x7F]),
+        (128, &[0x80, 0x01]),
+        (129, &[0x81, 0x01]),
+        (130, &[0x82, 0x01]),
+        (12857, &[0xB9, 0x64]),
+        (624485, &[0xE5, 0x8E, 0x26]),
+        (
+            0x0102_0304_0506_0708,
+            &[0x88, 0x8E, 0x98, 0xA8, 0xC0, 0xE0, 0x80, 0x81, 0x01],
+        ),
+        (
+            u64::MAX,
+            &[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01],
+        ),
+    ];
+
+    for &(val, encoded) in cases {
+        let mut buf = [0; 10];
+
+        let len = ULEB128::from(val).write_into(&mut buf).expect("write");
+        assert_eq!(&buf[..len], encoded, "{}", val);
+
+        let (result, len) = ULEB128::read_from(encoded).expect("read");
+        assert_eq!(u64::from(result), val);
+        assert_eq!(len, encoded.len());
+    }
+}
+
+#[quickcheck]
+fn qc_uleb128_read_as(val: u64) -> bool {
+    use std::convert::TryFrom;
+
+    let mut buf = [0; 10];
+    let len = ULEB128::from(val).write_into(&mut buf).expect("write");
+
+    let narrow = ULEB128::read_as::<u16>(&buf).map(|(val, _)| val).ok();
+    let wide = ULEB128::read_as::<u128>(&buf).expect("read");
+
+    narrow == u16::try_from(val).ok() && wide == (u128::from(val), len)
+}
+
+#[test]
+fn uleb128_read_as() {
+    let buf = [0x80, 0x02];
+
+    assert_eq!(ULEB128::read_as::<u16>(&buf), Ok((256, 2)));
+    assert_eq!(
+        ULEB128::read_as::<u8>(&buf),
+        Err(LEB128DecodeError::IntegerOverflow)
+    );
+    assert_eq!(
+        ULEB128::read_as::<u8>(&buf[..1]),
+        Err(LEB128DecodeError::BufferOverflow)
+    );
+}
+
+#[quickcheck]
+fn qc_sleb128_bit_inspection(val: i64) -> bool {
+    let encoded = SLEB128::from(val).to_encoded();
+    let (last, rest) = encoded.split_last().expect("non-empty");
+
+    rest.iter().all(|&byte| nano_leb128::is_continuation(byte))
+        && !nano_leb128::is_continuation(*last)
+        && nano_leb128::sign_bit_set(*last) == (val < 0)
+        && nano_leb128::payload(*last) == *last
+}
+
+#[cfg(feature = "std_io_ext")]
+#[quickcheck]
+fn qc_leb128_writer(vals: Vec<(u64, i64)>) -> bool {
+    use nano_leb128::LEB128Writer;
+
+    let mut expected = Vec::new();
+    let mut writer = LEB128Writer::with_capacity(16, Vec::new());
+
+    for &(u, s) in &vals {
+        ULEB128::from(u)
+            .write_into_std_io(&mut expected)
+            .expect("write");
+        SLEB128::from(s)
+            .write_into_std_io(&mut expected)
+            .expect("write");
+
+        writer.push(ULEB128::from(u)).expect("push");
+        writer.push_signed(SLEB128::from(s)).expect("push");
+    }
+
+    writer.into_inner().expect("flush") == expected
+}
+
+#[cfg(feature = "std_io_ext")]
+#[test]
+fn leb128_writer_batches_writes() {
+    use nano_leb128::LEB128Writer;
+    use std::io::{self, Write};
+
+    struct CountingWriter(usize);
+
+    impl Write for CountingWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0 += 1;
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut writer = LEB128Writer::new(CountingWriter(0));
+
+    for i in 0..1000 {
+        writer.push(ULEB128::from(i)).expect("push");
+    }
+
+    assert_eq!(writer.get_ref().0, 0);
+    writer.flush().expect("flush");
+    assert_eq!(writer.get_ref().0, 1);
+}
+
+#[quickcheck]
+fn qc_sleb128_lossy(val: i64) -> bool {
+    let encoded = SLEB128::from(val).to_encoded();
+    let expected = Ok((SLEB128::from(val), encoded.len()));
+
+    SLEB128::read_from_saturating(&encoded) == expected
+        && SLEB128::read_from_wrapping(&encoded) == expected
+}
+
+#[quickcheck]
+fn qc_uleb128_lossy(val: u64) -> bool {
+    let encoded = ULEB128::from(val).to_encoded();
+    let expected = Ok((ULEB128::from(val), encoded.len()));
+
+    ULEB128::read_from_saturating(&encoded) == expected
+        && ULEB128::read_from_wrapping(&encoded) == expected
+}
+
+#[test]
+fn sleb128_decode_lossy_overflow() {
+    // 2^64 + 5
+    let buf = [
+        0x85, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x82, 0x00, 0xFF,
+    ];
+    assert_eq!(
+        SLEB128::read_from_saturating(&buf),
+        Ok((SLEB128::from(i64::MAX), 11))
+    );
+    assert_eq!(
+        SLEB128::read_from_wrapping(&buf),
+        Ok((SLEB128::from(5), 11))
+    );
+
+    // -2^64 - 5
+    let buf = [
+        0xFB, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFD, 0x7F,
+    ];
+    assert_eq!(
+        SLEB128::read_from_saturating(&buf),
+        Ok((SLEB128::from(i64::MIN), 11))
+    );
+    assert_eq!(
+        SLEB128::read_from_wrapping(&buf),
+        Ok((SLEB128::from(-5), 11))
+    );
+
+    // 2^63, one past i64::MAX
+    let buf = [0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x01];
+    assert_eq!(
+        SLEB128::read_from_saturating(&buf),
+        Ok((SLEB128::from(i64::MAX), 10))
+    );
+    assert_eq!(
+        SLEB128::read_from_wrapping(&buf),
+        Ok((SLEB128::from(i64::MIN), 10))
+    );
+
+    assert_eq!(
+        SLEB128::read_from_saturating(&buf[..9]),
+        Err(LEB128DecodeError::BufferOverflow)
+    );
+}
+
+#[test]
+fn uleb128_decode_lossy_overflow() {
+    // 2^64 + 5
+    let buf = [
+        0x85, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x82, 0x00, 0xFF,
+    ];
+    assert_eq!(
+        ULEB128::read_from_saturating(&buf),
+        Ok((ULEB128::from(u64::MAX), 11))
+    );
+    assert_eq!(
+        ULEB128::read_from_wrapping(&buf),
+        Ok((ULEB128::from(5), 11))
+    );
+
+    assert_eq!(
+        ULEB128::read_from_wrapping(&buf[..10]),
+        Err(LEB128DecodeError::BufferOverflow)
+    );
+}
+
+#[cfg(feature = "std_io_ext")]
+#[quickcheck]
+fn qc_leb128_std_io_no_alloc(u: u64, s: i64) -> bool {
+    let mut buf = Vec::new();
+    ULEB128::from(u).write_into_std_io(&mut buf).expect("write");
+    SLEB128::from(s).write_into_std_io(&mut buf).expect("write");
+
+    let mut reader = &buf[..];
+    let (u0, n0) = ULEB128::read_from_std_io_no_alloc(&mut reader).expect("read");
+    let (s0, n1) = SLEB128::read_from_std_io_no_alloc(&mut reader).expect("read");
+
+    u64::from(u0) == u && i64::from(s0) == s && n0 + n1 == buf.len() && reader.is_empty()
+}
+
+#[cfg(feature = "std_io_ext")]
+#[test]
+fn leb128_std_io_no_alloc_errors() {
+    use std::io::ErrorKind;
+
+    let buf = [0x80, 0x80];
+    let err = ULEB128::read_from_std_io_no_alloc(&buf[..]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+    let buf = [0x80; 11];
+    let err = ULEB128::read_from_std_io_no_alloc(&buf[..]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    let err = SLEB128::read_from_std_io_no_alloc(&buf[..]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[quickcheck]
+fn qc_leb128_read_from_reporting(u: u64, s: i64) -> bool {
+    let u_encoded = ULEB128::from(u).to_encoded();
+    let s_encoded = SLEB128::from(s).to_encoded();
+
+    ULEB128::read_from_reporting(&u_encoded) == Ok((ULEB128::from(u), u_encoded.len()))
+        && SLEB128::read_from_reporting(&s_encoded) == Ok((SLEB128::from(s), s_encoded.len()))
+}
+
+#[test]
+fn leb128_read_from_reporting_errors() {
+    let buf = [0x80, 0x80, 0x80];
+    assert_eq!(
+        ULEB128::read_from_reporting(&buf),
+        Err((LEB128DecodeError::BufferOverflow, 3))
+    );
+    assert_eq!(
+        SLEB128::read_from_reporting(&buf),
+        Err((LEB128DecodeError::BufferOverflow, 3))
+    );
+
+    let buf = [
+        0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x02, 0x00,
+    ];
+    assert_eq!(
+        ULEB128::read_from_reporting(&buf),
+        Err((LEB128DecodeError::IntegerOverflow, 10))
+    );
+    assert_eq!(
+        SLEB128::read_from_reporting(&buf),
+        Err((LEB128DecodeError::IntegerOverflow, 10))
     );
 }
+
+#[test]
+fn leb128_single_byte_boundaries() {
+    for val in -65..=65 {
+        let mut buf = [0; 2];
+        let len = SLEB128::from(val).write_into(&mut buf).expect("write");
+
+        assert_eq!(len, if (-64..64).contains(&val) { 1 } else { 2 });
+        assert_eq!(SLEB128::read_from(&buf), Ok((SLEB128::from(val), len)));
+    }
+
+    for val in 0..=129 {
+        let mut buf = [0; 2];
+        let len = ULEB128::from(val).write_into(&mut buf).expect("write");
+
+        assert_eq!(len, if val < 128 { 1 } else { 2 });
+        assert_eq!(ULEB128::read_from(&buf), Ok((ULEB128::from(val), len)));
+    }
+
+    assert_eq!(
+        SLEB128::from(0).write_into(&mut []),
+        Err(LEB128EncodeError::BufferOverflow { needed: 1 })
+    );
+    assert_eq!(
+        ULEB128::from(0).write_into(&mut []),
+        Err(LEB128EncodeError::BufferOverflow { needed: 1 })
+    );
+}
+
+#[quickcheck]
+fn qc_sleb128_write_into_canonical(val: i64, shift: u8) -> bool {
+    // Spread the magnitude of the value across the full width.
+    let val = val.rotate_left(u32::from(shift));
+    let mut buf = [0; 10];
+
+    let len = SLEB128::from(val)
+        .write_into_canonical(&mut buf)
+        .expect("write");
+
+    len == SLEB128::from(val).encoded_len() && SLEB128::read_from(&buf[..len]).is_ok()
+}
+
+#[quickcheck]
+fn qc_uleb128_write_into_canonical(val: u64, shift: u8) -> bool {
+    // Spread the magnitude of the value across the full width.
+    let val = val.rotate_left(u32::from(shift));
+    let mut buf = [0; 10];
+
+    let len = ULEB128::from(val)
+        .write_into_canonical(&mut buf)
+        .expect("write");
+
+    len == ULEB128::from(val).encoded_len() && ULEB128::read_from(&buf[..len]).is_ok()
+}
+
+#[quickcheck]
+fn qc_leb128_encoded_len(signed: i64, unsigned: u64, shift: u8) -> bool {
+    // Spread the magnitude of the values across the full width.
+    let signed = signed.rotate_left(u32::from(shift));
+    let unsigned = unsigned.rotate_left(u32::from(shift));
+    let mut buf = [0; 10];
+
+    let signed_len = SLEB128::from(signed).write_into(&mut buf).expect("write");
+    let unsigned_len = ULEB128::from(unsigned).write_into(&mut buf).expect("write");
+
+    signed_len == SLEB128::from(signed).encoded_len()
+        && unsigned_len == ULEB128::from(unsigned).encoded_len()
+}
+
+#[test]
+fn leb128_encoded_len_boundaries() {
+    let mut buf = [0; 10];
+
+    for shift in 0..64 {
+        for &val in &[1u64 << shift, (1u64 << shift) - 1] {
+            let len = ULEB128::from(val).write_into(&mut buf).expect("write");
+            assert_eq!(ULEB128::from(val).encoded_len(), len);
+        }
+
+        for &val in &[
+            1i64 << shift,
+            (1i64 << shift).wrapping_sub(1),
+            (1i64 << shift).wrapping_neg(),
+        ] {
+            let len = SLEB128::from(val).write_into(&mut buf).expect("write");
+            assert_eq!(SLEB128::from(val).encoded_len(), len);
+        }
+    }
+}
+
+#[quickcheck]
+fn qc_uleb128_tlv(tag: u64, data: Vec<u8>) -> bool {
+    let mut buf = vec![0; 20 + data.len()];
+    let len = ULEB128::write_tlv(tag, &data, &mut buf).expect("write");
+
+    ULEB128::read_tlv(&buf) == Ok((tag, &data[..], len))
+        && ULEB128::write_tlv(tag, &data, &mut buf[..len - 1])
+            == Err(LEB128EncodeError::BufferOverflow { needed: len })
+}
+
+#[test]
+fn uleb128_tlv_buffer_overflow() {
+    let buf = [0x01, 0x04, 0xAA, 0xBB, 0xCC];
+    assert_eq!(
+        ULEB128::read_tlv(&buf),
+        Err(LEB128DecodeError::BufferOverflow)
+    );
+    assert_eq!(
+        ULEB128::read_tlv(&buf[..1]),
+        Err(LEB128DecodeError::BufferOverflow)
+    );
+    assert_eq!(
+        ULEB128::read_tlv(&buf[..2]),
+        Err(LEB128DecodeError::BufferOverflow)
+    );
+
+    let buf = [0x01, 0x00];
+    assert_eq!(ULEB128::read_tlv(&buf), Ok((1, &[][..], 2)));
+}
+
+#[cfg(feature = "byteio_ext")]
+#[quickcheck]
+fn qc_leb128_byteio_keep(a: i64, b: u64) -> bool {
+    let mut buf = [0; 20];
+    let mut len = SLEB128::from(a)
+        .write_into_byteio(&mut buf[..])
+        .expect("write");
+    len += ULEB128::from(b)
+        .write_into_byteio(&mut buf[len..])
+        .expect("write");
+
+    let (x, rest) = SLEB128::read_from_byteio_keep(&buf[..len]).expect("read");
+    let (y, rest) = ULEB128::read_from_byteio_keep(rest).expect("read");
+
+    i64::from(x) == a && u64::from(y) == b && rest.is_empty()
+}
+
+#[quickcheck]
+fn qc_sleb128_bytes(val: i64, rot: u32) -> bool {
+    let val = val.rotate_left(rot);
+    let bytes = SLEB128::from(val).bytes();
+
+    bytes.len() == SLEB128::from(val).encoded_len()
+        && bytes.eq(SLEB128::from(val).to_encoded().iter().copied())
+}
+
+#[quickcheck]
+fn qc_uleb128_bytes(val: u64, rot: u32) -> bool {
+    let val = val.rotate_left(rot);
+    let bytes = ULEB128::from(val).bytes();
+
+    bytes.len() == ULEB128::from(val).encoded_len()
+        && bytes.eq(ULEB128::from(val).to_encoded().iter().copied())
+}
+
+#[quickcheck]
+fn qc_leb128_buffer_overflow_needed(a: i64, b: u64, rot: u32) -> bool {
+    let (a, b) = (
+        SLEB128::from(a.rotate_left(rot)),
+        ULEB128::from(b.rotate_left(rot)),
+    );
+
+    let retry_s = match a.write_into(&mut [0; 10][..a.encoded_len() - 1]) {
+        Err(LEB128EncodeError::BufferOverflow { needed }) => a.write_into(&mut vec![0; needed]),
+        _ => return false,
+    };
+    let retry_u = match b.write_into(&mut [0; 10][..b.encoded_len() - 1]) {
+        Err(LEB128EncodeError::BufferOverflow { needed }) => b.write_into(&mut vec![0; needed]),
+        _ => return false,
+    };
+
+    retry_s == Ok(a.encoded_len()) && retry_u == Ok(b.encoded_len())
+}
+
+#[cfg(feature = "std_io_ext")]
+#[quickcheck]
+fn qc_leb128_std_io_with(vals: Vec<(i64, u64)>) -> bool {
+    let mut buf = Vec::new();
+
+    for &(a, b) in &vals {
+        SLEB128::from(a).write_into_std_io(&mut buf).expect("write");
+        ULEB128::from(b).write_into_std_io(&mut buf).expect("write");
+    }
+
+    let mut reader = &*buf;
+    let mut scratch = vec![0xFF; 32];
+
+    vals.iter().all(|&(a, b)| {
+        let (x, n) = SLEB128::read_from_std_io_with(&mut reader, &mut scratch).expect("read");
+        let signed = i64::from(x) == a && scratch.len() == n;
+
+        let (y, n) = ULEB128::read_from_std_io_with(&mut reader, &mut scratch).expect("read");
+        let unsigned = u64::from(y) == b && scratch.len() == n;
+
+        signed && unsigned
+    }) && reader.is_empty()
+}
+
+#[test]
+fn uleb128_encode_small() {
+    for value in 0..=u8::MAX {
+        let encoded = ULEB128::from(u64::from(value)).to_encoded();
+
+        match ULEB128::encode_small(value) {
+            Some(byte) => assert_eq!(*encoded, [byte]),
+            None => assert!(value >= 0x80 && encoded.len() == 2),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[quickcheck]
+fn qc_leb128_append_to_vec(a: i64, b: u64, rot: u32) -> bool {
+    let (a, b) = (a.rotate_left(rot), b.rotate_left(rot));
+    let mut buf = Vec::new();
+
+    let n0 = SLEB128::from(a).append_to_vec(&mut buf);
+    let n1 = ULEB128::from(b).append_to_vec(&mut buf);
+
+    let (x, m0) = SLEB128::read_from(&buf).expect("read");
+    let (y, m1) = ULEB128::read_from(&buf[m0..]).expect("read");
+
+    i64::from(x) == a && u64::from(y) == b && n0 == m0 && n1 == m1 && buf.len() == n0 + n1
+}
+
+#[test]
+fn leb128_read_from_or_default() {
+    assert_eq!(
+        SLEB128::read_from_or_default(&[]),
+        Ok((SLEB128::from(0), 0))
+    );
+    assert_eq!(
+        ULEB128::read_from_or_default(&[]),
+        Ok((ULEB128::from(0), 0))
+    );
+
+    assert_eq!(
+        SLEB128::read_from_or_default(&[0xC0, 0xBB, 0x78]),
+        Ok((SLEB128::from(-123456), 3))
+    );
+    assert_eq!(
+        ULEB128::read_from_or_default(&[0xE5, 0x8E, 0x26]),
+        Ok((ULEB128::from(624485), 3))
+    );
+
+    assert_eq!(
+        SLEB128::read_from_or_default(&[0x80]),
+        Err(LEB128DecodeError::BufferOverflow)
+    );
+    assert_eq!(
+        ULEB128::read_from_or_default(&[0x80]),
+        Err(LEB128DecodeError::BufferOverflow)
+    );
+}
+
+#[quickcheck]
+fn qc_leb128_reinterpret(val: i64, rot: u32) -> bool {
+    let val = val.rotate_left(rot);
+    let unsigned = SLEB128::from(val).reinterpret_unsigned();
+
+    u64::from(unsigned) == val as u64 && i64::from(unsigned.reinterpret_signed()) == val
+}
+
+#[quickcheck]
+fn qc_leb128_is_valid_encoding(a: i64, b: u64, rot: u32) -> bool {
+    let (a, b) = (
+        SLEB128::from(a.rotate_left(rot)),
+        ULEB128::from(b.rotate_left(rot)),
+    );
+    let (sa, ub) = (a.to_encoded(), b.to_encoded());
+
+    let mut trailing = sa.to_vec();
+    trailing.push(0);
+
+    SLEB128::is_valid_encoding(&sa)
+        && ULEB128::is_valid_encoding(&ub)
+        && !SLEB128::is_valid_encoding(&sa[..sa.len() - 1])
+        && !ULEB128::is_valid_encoding(&ub[..ub.len() - 1])
+        && !SLEB128::is_valid_encoding(&trailing)
+}
+
+#[test]
+fn leb128_is_valid_encoding_overflow() {
+    let mut buf = [0xFF; 10];
+
+    buf[9] = 0x01;
+    assert!(ULEB128::is_valid_encoding(&buf));
+    assert!(!SLEB128::is_valid_encoding(&buf));
+
+    buf[9] = 0x02;
+    assert!(!ULEB128::is_valid_encoding(&buf));
+}
+
+#[test]
+fn uleb128_duration() {
+    use nano_leb128::DurationUnit;
+    use std::time::Duration;
+
+    let d = Duration::new(3, 123_456_789);
+
+    assert_eq!(u64::from(ULEB128::from_duration(d, DurationUnit::Secs)), 3);
+    assert_eq!(
+        u64::from(ULEB128::from_duration(d, DurationUnit::Millis)),
+        3_123
+    );
+    assert_eq!(
+        u64::from(ULEB128::from_duration(d, DurationUnit::Micros)),
+        3_123_456
+    );
+    assert_eq!(
+        u64::from(ULEB128::from_duration(d, DurationUnit::Nanos)),
+        3_123_456_789
+    );
+
+    for &unit in &[
+        DurationUnit::Secs,
+        DurationUnit::Millis,
+        DurationUnit::Micros,
+        DurationUnit::Nanos,
+    ] {
+        let value = ULEB128::from_duration(Duration::MAX, unit);
+        assert_eq!(u64::from(value), u64::MAX, "{:?}", unit);
+
+        let value = ULEB128::from(624485);
+        assert_eq!(ULEB128::from_duration(value.to_duration(unit), unit), value);
+    }
+}
+
+#[quickcheck]
+fn qc_leb128_read_array_from(a: (i64, i64, i64), b: (u64, u64, u64)) -> bool {
+    let mut sbuf = [0; 30];
+    let mut ubuf = [0; 30];
+    let mut slen = 0;
+    let mut ulen = 0;
+
+    for &val in &[a.0, a.1, a.2] {
+        slen += SLEB128::from(val)
+            .write_into(&mut sbuf[slen..])
+            .expect("write");
+    }
+    for &val in &[b.0, b.1, b.2] {
+        ulen += ULEB128::from(val)
+            .write_into(&mut ubuf[ulen..])
+            .expect("write");
+    }
+
+    let (sbuf, ubuf) = (&sbuf[..slen], &ubuf[..ulen]);
+
+    SLEB128::read_array_from::<3>(sbuf) == Ok(([a.0, a.1, a.2], slen))
+        && ULEB128::read_array_from::<3>(ubuf) == Ok(([b.0, b.1, b.2], ulen))
+        && SLEB128::read_array_from::<0>(sbuf) == Ok(([], 0))
+        && SLEB128::read_array_from::<4>(sbuf) == Err(LEB128DecodeError::BufferOverflow)
+        && ULEB128::read_array_from::<4>(ubuf) == Err(LEB128DecodeError::BufferOverflow)
+}
+
+#[quickcheck]
+fn qc_leb128_groups(a: i64, b: u64, rot: u32) -> bool {
+    let (a, b) = (a.rotate_left(rot), b.rotate_left(rot));
+
+    let unsigned = ULEB128::from(b)
+        .groups()
+        .enumerate()
+        .fold(0u64, |acc, (i, group)| {
+            acc | u64::from(group).wrapping_shl(7 * i as u32)
+        });
+
+    SLEB128::from(a).groups().count() == SLEB128::from(a).encoded_len()
+        && SLEB128::from(a).groups().all(|group| group < 0x80)
+        && unsigned == b
+}
+
+#[quickcheck]
+fn qc_leb128_read_dynamic(signed: i64, unsigned: u64) -> bool {
+    let mut buf = [0; 10];
+
+    let n0 = SLEB128::from(signed).write_into(&mut buf).expect("write");
+    let signed_ok = nano_leb128::read_dynamic(&buf, true) == Ok((i128::from(signed), n0));
+
+    let n0 = ULEB128::from(unsigned).write_into(&mut buf).expect("write");
+    let unsigned_ok = nano_leb128::read_dynamic(&buf, false) == Ok((i128::from(unsigned), n0));
+
+    signed_ok && unsigned_ok
+}
+
+#[test]
+fn leb128_read_dynamic_error() {
+    assert_eq!(
+        nano_leb128::read_dynamic(&[0x80], true),
+        Err(LEB128DecodeError::BufferOverflow)
+    );
+    assert_eq!(
+        nano_leb128::read_dynamic(&[0x80], false),
+        Err(LEB128DecodeError::BufferOverflow)
+    );
+}
+
+#[cfg(feature = "std")]
+#[quickcheck]
+fn qc_sleb128_for_each_in(vals: Vec<i64>) -> bool {
+    let mut buf = Vec::new();
+
+    for &val in &vals {
+        SLEB128::from(val).append_to_vec(&mut buf);
+    }
+
+    let mut decoded = Vec::new();
+    let len = SLEB128::for_each_in(&buf, |val| decoded.push(val)).expect("read");
+
+    decoded == vals && len == buf.len()
+}
+
+#[cfg(feature = "std")]
+#[quickcheck]
+fn qc_uleb128_for_each_in(vals: Vec<u64>) -> bool {
+    let mut buf = Vec::new();
+
+    for &val in &vals {
+        ULEB128::from(val).append_to_vec(&mut buf);
+    }
+
+    let mut decoded = Vec::new();
+    let len = ULEB128::for_each_in(&buf, |val| decoded.push(val)).expect("read");
+
+    decoded == vals && len == buf.len()
+}
+
+#[test]
+fn uleb128_for_each_in_truncated() {
+    let mut decoded = Vec::new();
+
+    assert_eq!(
+        ULEB128::for_each_in(&[0x01, 0x02, 0x80], |val| decoded.push(val)),
+        Err(LEB128DecodeError::BufferOverflow)
+    );
+    assert_eq!(decoded, [1, 2]);
+}
+
+#[quickcheck]
+fn qc_sleb128_128(val: i128) -> bool {
+    let mut buf = [0; 19];
+
+    let n0 = SLEB128_128::from(val).write_into(&mut buf).expect("write");
+    let (result, n1) = SLEB128_128::read_from(&buf).expect("read");
+
+    i128::from(result) == val && n0 == n1 && n0 == SLEB128_128::from(val).encoded_len()
+}
+
+#[quickcheck]
+fn qc_uleb128_128(val: u128) -> bool {
+    let mut buf = [0; 19];
+
+    let n0 = ULEB128_128::from(val).write_into(&mut buf).expect("write");
+    let (result, n1) = ULEB128_128::read_from(&buf).expect("read");
+
+    u128::from(result) == val && n0 == n1 && n0 == ULEB128_128::from(val).encoded_len()
+}
+
+#[quickcheck]
+fn qc_leb128_128_matches_64(signed: i64, unsigned: u64) -> bool {
+    let mut buf64 = [0; 10];
+    let mut buf128 = [0; 19];
+
+    let n64 = SLEB128::from(signed).write_into(&mut buf64).expect("write");
+    let n128 = SLEB128_128::from(SLEB128::from(signed))
+        .write_into(&mut buf128)
+        .expect("write");
+    let signed_ok = buf64[..n64] == buf128[..n128];
+
+    let n64 = ULEB128::from(unsigned)
+        .write_into(&mut buf64)
+        .expect("write");
+    let n128 = ULEB128_128::from(ULEB128::from(unsigned))
+        .write_into(&mut buf128)
+        .expect("write");
+    let unsigned_ok = buf64[..n64] == buf128[..n128];
+
+    signed_ok && unsigned_ok
+}
+
+#[test]
+fn leb128_128_bounds() {
+    let mut buf = [0; 19];
+
+    for &val in &[i128::MIN, i128::MAX, -1, 0] {
+        let len = SLEB128_128::from(val).write_into(&mut buf).expect("write");
+        assert_eq!(SLEB128_128::read_from(&buf[..len]), Ok((val.into(), len)));
+    }
+
+    let mut overflow = [0xFF; 19];
+    overflow[18] = 0x04;
+    assert_eq!(
+        ULEB128_128::read_from(&overflow),
+        Err(LEB128DecodeError::IntegerOverflow)
+    );
+
+    overflow[18] = 0x03;
+    assert_eq!(
+        ULEB128_128::read_from(&overflow),
+        Ok((u128::MAX.into(), 19))
+    );
+
+    // The bits above bit 127 must extend the sign.
+    overflow[18] = 0x02;
+    assert_eq!(
+        SLEB128_128::read_from(&overflow),
+        Err(LEB128DecodeError::IntegerOverflow)
+    );
+
+    assert_eq!(
+        ULEB128_128::from(1).write_into(&mut []),
+        Err(LEB128EncodeError::BufferOverflow { needed: 1 })
+    );
+}
+
+#[derive(Debug, PartialEq)]
+struct PageCount(u64);
+
+impl LEB128Decode for PageCount {
+    fn leb128_decode_fn<F: FnMut() -> Option<u8>>(next: F) -> Result<Self, LEB128DecodeError> {
+        ULEB128::leb128_decode_fn(next).map(|pages| PageCount(u64::from(pages)))
+    }
+}
+
+impl LEB128Encode for PageCount {
+    fn leb128_encode_fn<G: FnMut(u8) -> bool>(self, push: G) -> Result<(), LEB128EncodeError> {
+        ULEB128::from(self.0).leb128_encode_fn(push)
+    }
+}
+
+#[quickcheck]
+fn qc_leb128_custom_type(val: u64) -> bool {
+    let mut buf = [0; 10];
+    let mut expected = [0; 10];
+
+    PageCount(val).leb128_encode(&mut buf[..]).expect("write");
+    ULEB128::from(val).write_into(&mut expected).expect("write");
+
+    buf == expected && PageCount::leb128_decode(&buf[..]) == Ok(PageCount(val))
+}
+
+#[quickcheck]
+fn qc_sleb128_zigzag(val: i64) -> bool {
+    let mut buf = [0; 10];
+
+    let n0 = SLEB128::from(val)
+        .write_into_zigzag(&mut buf)
+        .expect("write");
+    let (result, n1) = SLEB128::read_from_zigzag(&buf).expect("read");
+
+    i64::from(result) == val && n0 == n1
+}
+
+#[test]
+fn sleb128_zigzag_small_magnitudes() {
+    let mut buf = [0; 10];
+
+    for &(val, byte) in &[(0, 0x00), (-1, 0x01), (1, 0x02), (-64, 0x7F), (63, 0x7E)] {
+        assert_eq!(SLEB128::from(val).write_into_zigzag(&mut buf), Ok(1));
+        assert_eq!(buf[0], byte);
+    }
+
+    assert_eq!(SLEB128::from(64).write_into_zigzag(&mut buf), Ok(2));
+    assert_eq!(SLEB128::from(-65).write_into_zigzag(&mut buf), Ok(2));
+}
+
+#[quickcheck]
+fn qc_leb128_read_from_canonical(signed: i64, unsigned: u64) -> bool {
+    let mut buf = [0; 10];
+
+    let len = SLEB128::from(signed).write_into(&mut buf).expect("write");
+    let signed_ok = SLEB128::read_from_canonical(&buf) == Ok((signed.into(), len));
+
+    let len = ULEB128::from(unsigned).write_into(&mut buf).expect("write");
+    let unsigned_ok = ULEB128::read_from_canonical(&buf) == Ok((unsigned.into(), len));
+
+    signed_ok && unsigned_ok
+}
+
+#[test]
+fn leb128_read_from_canonical_overlong() {
+    assert_eq!(
+        ULEB128::read_from_canonical(&[0x80, 0x00]),
+        Err(LEB128DecodeError::Overlong)
+    );
+    assert_eq!(
+        ULEB128::read_from_canonical(&[0xFF, 0x80, 0x00]),
+        Err(LEB128DecodeError::Overlong)
+    );
+    assert_eq!(ULEB128::read_from(&[0x80, 0x00]), Ok((0.into(), 2)));
+
+    // Redundant sign extension of -1 and 0.
+    assert_eq!(
+        SLEB128::read_from_canonical(&[0xFF, 0x7F]),
+        Err(LEB128DecodeError::Overlong)
+    );
+    assert_eq!(
+        SLEB128::read_from_canonical(&[0x80, 0x00]),
+        Err(LEB128DecodeError::Overlong)
+    );
+    assert_eq!(SLEB128::read_from(&[0xFF, 0x7F]), Ok(((-1).into(), 2)));
+
+    // The extra byte is required to carry the sign.
+    assert_eq!(
+        SLEB128::read_from_canonical(&[0xC0, 0x00]),
+        Ok((64.into(), 2))
+    );
+    assert_eq!(
+        SLEB128::read_from_canonical(&[0x80, 0x7F]),
+        Ok(((-128).into(), 2))
+    );
+}
+
+#[quickcheck]
+fn qc_leb128_write_into_padded(signed: i64, unsigned: u64, pad_to: u8) -> bool {
+    let pad_to = 1 + usize::from(pad_to % 12);
+    let mut buf = [0; 12];
+
+    let signed = SLEB128::from(signed);
+    let signed_ok = match signed.write_into_padded(&mut buf, pad_to) {
+        Ok(len) => len == pad_to && SLEB128::read_from(&buf) == Ok((signed, pad_to)),
+        Err(LEB128EncodeError::Overlong) => pad_to > 10,
+        Err(err) => {
+            pad_to <= 10
+                && signed.encoded_len() > pad_to
+                && err
+                    == LEB128EncodeError::BufferOverflow {
+                        needed: signed.encoded_len(),
+                    }
+        }
+    };
+
+    let unsigned = ULEB128::from(unsigned);
+    let unsigned_ok = match unsigned.write_into_padded(&mut buf, pad_to) {
+        Ok(len) => len == pad_to && ULEB128::read_from(&buf) == Ok((unsigned, pad_to)),
+        Err(LEB128EncodeError::Overlong) => pad_to > 10,
+        Err(err) => {
+            pad_to <= 10
+                && unsigned.encoded_len() > pad_to
+                && err
+                    == LEB128EncodeError::BufferOverflow {
+                        needed: unsigned.encoded_len(),
+                    }
+        }
+    };
+
+    signed_ok && unsigned_ok
+}
+
+#[test]
+fn leb128_write_into_padded() {
+    let mut buf = [0; 5];
+
+    assert_eq!(SLEB128::from(-1).write_into_padded(&mut buf, 3), Ok(3));
+    assert_eq!(buf[..3], [0xFF, 0xFF, 0x7F]);
+    assert_eq!(SLEB128::read_from(&buf[..3]), Ok(((-1).into(), 3)));
+
+    assert_eq!(SLEB128::from(64).write_into_padded(&mut buf, 4), Ok(4));
+    assert_eq!(buf[..4], [0xC0, 0x80, 0x80, 0x00]);
+    assert_eq!(SLEB128::read_from(&buf[..4]), Ok((64.into(), 4)));
+
+    assert_eq!(ULEB128::from(624485).write_into_padded(&mut buf, 5), Ok(5));
+    assert_eq!(buf, [0xE5, 0x8E, 0xA6, 0x80, 0x00]);
+
+    assert_eq!(
+        ULEB128::from(624485).write_into_padded(&mut buf, 2),
+        Err(LEB128EncodeError::BufferOverflow { needed: 3 })
+    );
+    assert_eq!(
+        ULEB128::from(1).write_into_padded(&mut buf, 6),
+        Err(LEB128EncodeError::BufferOverflow { needed: 6 })
+    );
+    assert_eq!(
+        SLEB128::from(0).write_into_padded(&mut [0; 11], 11),
+        Err(LEB128EncodeError::Overlong)
+    );
+}
+
+#[test]
+fn leb128_iter_from() {
+    let mut buf = [0; 32];
+    let mut len = 0;
+
+    for &val in &[0, -1, 624485, i64::MIN] {
+        len += SLEB128::from(val)
+            .write_into(&mut buf[len..])
+            .expect("write");
+    }
+
+    let values: Result<Vec<_>, _> = SLEB128::iter_from(&buf[..len]).collect();
+    assert_eq!(values, Ok(vec![0, -1, 624485, i64::MIN]));
+
+    // A truncated tail is only reported once the complete values are read.
+    buf[len] = 0x80;
+    let mut iter = SLEB128::iter_from(&buf[..len + 1]);
+    assert_eq!(iter.by_ref().take(4).count(), 4);
+    assert_eq!(iter.next(), Some(Err(LEB128DecodeError::BufferOverflow)));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.bytes_consumed(), len);
+
+    let mut len = 0;
+
+    for &val in &[0, 127, 624485, u64::MAX] {
+        len += ULEB128::from(val)
+            .write_into(&mut buf[len..])
+            .expect("write");
+    }
+
+    let mut iter = ULEB128::iter_from(&buf[..len]);
+    assert_eq!(
+        iter.by_ref().collect::<Result<Vec<_>, _>>(),
+        Ok(vec![0, 127, 624485, u64::MAX])
+    );
+    assert_eq!(iter.bytes_consumed(), len);
+
+    buf[len] = 0xFF;
+    let mut iter = ULEB128::iter_from(&buf[..len + 1]);
+    assert_eq!(iter.by_ref().filter(Result::is_ok).count(), 4);
+    assert_eq!(iter.bytes_consumed(), len);
+
+    assert_eq!(ULEB128::iter_from(&[]).next(), None);
+}
@@ -10,9 +10,33 @@
 //! let _ = s.send("hello");
 //! assert_eq!(r.recv().unwrap(), "hello");
 //! ```
+//!
+//! # Features
+//!
+//! * `async`
+//!
+//!   Adds methods that integrate with [`std::future`].
+//!
+//! * `debug-wakeups`
+//!
+//!   Counts spurious wakeups of blocking receives, for diagnosing latency
+//!   spikes. See [`Receiver::spurious_wakeups`].
+//!
+//! [`std::future`]: https://doc.rust-lang.org/std/future/index.html
+//! [`Receiver::spurious_wakeups`]: struct.Receiver.html#method.spurious_wakeups
 
-use std::sync::Arc;
-use std::time::Duration;
+use std::any::Any;
+#[cfg(feature = "async")]
+use std::future::Future;
+use std::iter;
+#[cfg(feature = "async")]
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::{mpsc, Arc, Weak};
+#[cfg(feature = "async")]
+use std::task::{Context, Poll, Waker};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
 use parking_lot::{Condvar, Mutex, MutexGuard};
 
@@ -37,151 +61,2343 @@ use parking_lot::{Condvar, Mutex, MutexGuard};
 /// assert_eq!(r.recv_timeout(Duration::from_secs(3)).unwrap(), 128);
 /// ```
 pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
-    let mutex = Arc::new(Mutex::new(None));
-    let condvar = Arc::new(Condvar::new());
+    channel_with_progress()
+}
+
+/// Creates a new one-shot channel with the given identifier.
+///
+/// Both halves report `id` from their `id` methods, so that the send and
+/// receive of a value can be correlated, e.g. when tracing. Channels created
+/// by [`channel`] are assigned identifiers from a process-wide counter, which
+/// may collide with identifiers chosen here.
+///
+/// # Examples
+///
+/// ```
+/// let (s, r) = ::nano_oneshot::channel_with_id::<i32>(42);
+///
+/// assert_eq!(s.id(), 42);
+/// assert_eq!(r.id(), 42);
+/// ```
+///
+/// [`channel`]: fn.channel.html
+pub fn channel_with_id<T>(id: u64) -> (Sender<T>, Receiver<T>) {
+    let inner = Arc::new(Inner::with_id(id));
+
+    let s = Sender(Arc::clone(&inner));
+    let r = Receiver(inner, None);
+
+    (s, r)
+}
 
-    let mutex2 = Arc::clone(&mutex);
-    let condvar2 = Arc::clone(&condvar);
+/// Creates a new one-shot channel with a progress side-channel.
+///
+/// In addition to the final value of type `T`, the sender may publish
+/// intermediate progress updates of type `P`. Only the latest progress update
+/// is kept.
+///
+/// # Examples
+///
+/// ```
+/// let (s, r) = ::nano_oneshot::channel_with_progress::<&str, u8>();
+///
+/// assert_eq!(r.poll_progress(), None);
+///
+/// s.progress(50);
+/// s.progress(100);
+/// assert_eq!(r.poll_progress(), Some(100));
+///
+/// let _ = s.send("done");
+/// assert_eq!(r.recv().unwrap(), "done");
+/// ```
+pub fn channel_with_progress<T, P>() -> (Sender<T, P>, Receiver<T, P>) {
+    let inner = Arc::new(Inner::new());
 
-    let s = Sender(mutex, condvar);
-    let r = Receiver(mutex2, condvar2);
+    let s = Sender(Arc::clone(&inner));
+    let r = Receiver(inner, None);
 
     (s, r)
 }
 
-/// The sending half of a one-shot channel.
+/// Creates a new one-shot channel with two competing senders.
 ///
-/// Senders are created by the [`channel`] function.
+/// Whichever sender sends first delivers its value to the receiver. The other
+/// sender's value is returned to it in [`SendError::AlreadySent`]. The channel
+/// is only disconnected once both senders have been dropped without sending.
+///
+/// # Examples
+///
+/// ```
+/// use nano_oneshot::SendError;
+///
+/// let (primary, backup, r) = ::nano_oneshot::channel_race();
+///
+/// let _ = backup.send("backup");
+/// assert_eq!(primary.send("primary"), Err(SendError::AlreadySent("primary")));
+///
+/// assert_eq!(r.recv().unwrap(), "backup");
+/// ```
+///
+/// [`SendError::AlreadySent`]: enum.SendError.html#variant.AlreadySent
+pub fn channel_race<T>() -> (Sender<T>, Sender<T>, Receiver<T>) {
+    let (s, r) = channel();
+    let Sender(inner) = &s;
+
+    inner.state.lock().senders += 1;
+    let other = Sender(Arc::clone(inner));
+
+    (s, other, r)
+}
+
+/// Creates a new zero-capacity one-shot channel.
+///
+/// Unlike [`channel`] the value is never stored: [`Sender::send`] blocks until
+/// the receiver is parked in a blocking receive, then hands the value over
+/// directly. If the receiver is dropped first then `send` returns
+/// [`SendError::Disconnected`].
+///
+/// The non-blocking receive methods never park, so a sender will not hand off
+/// to a receiver that only polls.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+///
+/// let (s, r) = ::nano_oneshot::channel_rendezvous();
+///
+/// let handle = thread::spawn(move || s.send("hello"));
+///
+/// assert_eq!(r.recv().unwrap(), "hello");
+/// assert_eq!(handle.join().unwrap(), Ok(()));
+/// ```
+///
+/// [`channel`]: fn.channel.html
+/// [`Sender::send`]: struct.Sender.html#method.send
+/// [`SendError::Disconnected`]: enum.SendError.html#variant.Disconnected
+pub fn channel_rendezvous<T>() -> (Sender<T>, Receiver<T>) {
+    let (s, r) = channel();
+    let Sender(inner) = &s;
+
+    inner.state.lock().rendezvous = true;
+
+    (s, r)
+}
+
+/// Creates a new one-shot channel which can be interrupted.
+///
+/// In addition to the two halves returned by [`channel`], an [`Interrupt`]
+/// handle is returned. Triggering the handle wakes a blocked receiver, causing
+/// it to return an `Interrupted` error.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+///
+/// use nano_oneshot::RecvError;
+///
+/// let (_s, r, interrupt) = ::nano_oneshot::channel_interruptible::<u8>();
+///
+/// thread::spawn(move || interrupt.trigger());
+///
+/// assert_eq!(r.recv(), Err(RecvError::Interrupted));
+/// ```
+///
+/// [`channel`]: fn.channel.html
+/// [`Interrupt`]: struct.Interrupt.html
+pub fn channel_interruptible<T>() -> (Sender<T>, Receiver<T>, Interrupt)
+where
+    T: Send + 'static,
+{
+    let (s, r) = channel();
+    let Receiver(inner, _) = &r;
+    let interrupt = Interrupt(Arc::downgrade(inner) as Weak<dyn Interruptible>);
+
+    (s, r, interrupt)
+}
+
+/// Creates a new one-shot channel which is interrupted when `handle` is shut
+/// down.
+///
+/// This behaves like [`channel_interruptible`], except that one
+/// [`ShutdownHandle`] can interrupt many channels at once. A channel created
+/// after the handle has been shut down is interrupted immediately.
+///
+/// # Examples
+///
+/// ```
+/// use nano_oneshot::{RecvError, ShutdownHandle};
+///
+/// let handle = ShutdownHandle::new();
+///
+/// let (_s0, r0) = ::nano_oneshot::channel_with_shutdown::<u8>(&handle);
+/// let (_s1, r1) = ::nano_oneshot::channel_with_shutdown::<u16>(&handle);
+///
+/// handle.shutdown();
+///
+/// assert_eq!(r0.recv(), Err(RecvError::Interrupted));
+/// assert_eq!(r1.recv(), Err(RecvError::Interrupted));
+/// ```
+///
+/// [`channel_interruptible`]: fn.channel_interruptible.html
+/// [`ShutdownHandle`]: struct.ShutdownHandle.html
+pub fn channel_with_shutdown<T>(handle: &ShutdownHandle) -> (Sender<T>, Receiver<T>)
+where
+    T: Send + 'static,
+{
+    let (s, r) = channel();
+    let Receiver(inner, _) = &r;
+
+    handle.register(Arc::downgrade(inner) as Weak<dyn Interruptible>);
+
+    (s, r)
+}
+
+/// Creates a new one-shot channel within caller-provided storage.
+///
+/// Unlike [`channel`] this does not allocate. The two halves borrow `slot`,
+/// and the slot may be reused for another channel once both halves have been
+/// dropped.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+///
+/// use nano_oneshot::OneshotSlot;
+///
+/// let mut slot = OneshotSlot::new();
+///
+/// for i in 0..3 {
+///     let (s, r) = ::nano_oneshot::channel_in(&mut slot);
+///
+///     thread::scope(|scope| {
+///         scope.spawn(move || s.send(i));
+///         assert_eq!(r.recv().unwrap(), i);
+///     });
+/// }
+/// ```
 ///
 /// [`channel`]: fn.channel.html
+pub fn channel_in<T>(
+    slot: &mut OneshotSlot<T>,
+) -> (BorrowedSender<'_, T>, BorrowedReceiver<'_, T>) {
+    let OneshotSlot(inner) = slot;
+    inner.reset();
+
+    let inner = &*inner;
+
+    (BorrowedSender(inner), BorrowedReceiver(inner))
+}
+
+/// Spawns a new thread which runs `f`, returning a receiver for the result.
+///
+/// If the receiver is dropped before `f` completes then the result is
+/// discarded.
+///
+/// # Examples
+///
+/// ```
+/// let r = ::nano_oneshot::spawn(|| 64 * 2);
+///
+/// assert_eq!(r.recv().unwrap(), 128);
+/// ```
+pub fn spawn<T, F>(f: F) -> Receiver<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (s, r) = channel();
+
+    thread::spawn(move || {
+        let _ = s.send(f());
+    });
+
+    r
+}
+
+/// The state shared between both halves of a one-shot channel.
 #[derive(Debug)]
-pub struct Sender<T>(Arc<Mutex<Option<T>>>, Arc<Condvar>);
+struct Inner<T, P> {
+    state: Mutex<State<T, P>>,
+    condvar: Condvar,
+    /// A [`ChannelState`] mirroring `state`, readable without the lock.
+    ///
+    /// It is only ever written while `state` is locked.
+    ///
+    /// [`ChannelState`]: enum.ChannelState.html
+    summary: AtomicU8,
+    id: u64,
+    /// The pool to return this allocation to once both halves are dropped.
+    pool: Option<Weak<Recycled<T, P>>>,
+}
 
-impl<T> Sender<T> {
-    /// Sends a value through the one-shot channel.
+/// The allocations of completed channels, waiting to be reused by a
+/// [`OneshotPool`].
+///
+/// [`OneshotPool`]: struct.OneshotPool.html
+type Recycled<T, P> = Mutex<Vec<Arc<Inner<T, P>>>>;
+
+/// The identifier of the next channel created without an explicit identifier.
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// The channel slot, along with the latest progress update and the liveness
+/// of each half.
+///
+/// `sent` remains set once a value has been sent, even after the value has
+/// been taken out of the slot, so that at most one value is ever delivered.
+///
+/// `sent_at` records when the value was stored, for a [`DeliveryToken`].
+///
+/// `interrupted` is set by an [`Interrupt`] and remains set, so a receiver
+/// which starts waiting after the trigger is interrupted too.
+///
+/// `watcher` is the signal of a [`Select`] waiting on the receiver, if any.
+///
+/// `senders` counts the live sending halves; the channel is disconnected once
+/// it reaches zero.
+///
+/// `waiting` is set while the receiver is parked on the condition variable,
+/// so that state changes only signal the condition variable when someone is
+/// there to be woken. It is only read and written with the lock held, and a
+/// receiver rechecks the state under the same lock before parking, so no
+/// wakeup can be missed.
+///
+/// `sender_waker` is the waker of a [`Sender::send_async`] future, which is
+/// woken once the value is taken or the receiver is dropped. Likewise
+/// `receiver_waker` is the waker of the last [`Receiver::poll_recv`], which is
+/// woken alongside the condition variable.
+///
+/// `rendezvous` makes a sender wait for `waiting` before handing off. The
+/// sender then parks on the same condition variable, so the receiver notifies
+/// all waiters when it parks or is dropped.
+///
+/// `spurious_wakeups` counts the times a blocking receive was woken with
+/// nothing to receive, the channel still connected and not interrupted.
+///
+/// [`DeliveryToken`]: struct.DeliveryToken.html
+/// [`Interrupt`]: struct.Interrupt.html
+/// [`Select`]: struct.Select.html
+/// [`Sender::send_async`]: struct.Sender.html#method.send_async
+/// [`Receiver::poll_recv`]: struct.Receiver.html#method.poll_recv
+#[derive(Debug)]
+struct State<T, P> {
+    value: Option<T>,
+    progress: Option<P>,
+    sent: bool,
+    interrupted: bool,
+    waiting: bool,
+    watcher: Option<Arc<Signal>>,
+    senders: usize,
+    receiver: bool,
+    rendezvous: bool,
+    sent_at: Option<Instant>,
+    #[cfg(feature = "debug-wakeups")]
+    spurious_wakeups: usize,
+    #[cfg(feature = "async")]
+    sender_waker: Option<Waker>,
+    #[cfg(feature = "async")]
+    receiver_waker: Option<Waker>,
+}
+
+impl<T, P> State<T, P> {
+    fn new() -> Self {
+        Self {
+            value: None,
+            progress: None,
+            sent: false,
+            interrupted: false,
+            waiting: false,
+            watcher: None,
+            senders: 1,
+            receiver: true,
+            rendezvous: false,
+            sent_at: None,
+            #[cfg(feature = "debug-wakeups")]
+            spurious_wakeups: 0,
+            #[cfg(feature = "async")]
+            sender_waker: None,
+            #[cfg(feature = "async")]
+            receiver_waker: None,
+        }
+    }
+
+    #[cfg(feature = "async")]
+    fn wake_sender(&mut self) {
+        if let Some(waker) = self.sender_waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<T, P> Inner<T, P> {
+    fn new() -> Self {
+        Self::with_id(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn with_id(id: u64) -> Self {
+        Self {
+            state: Mutex::new(State::new()),
+            condvar: Condvar::new(),
+            summary: AtomicU8::new(ChannelState::Empty as u8),
+            id,
+            pool: None,
+        }
+    }
+
+    /// Resets the state for a new channel.
+    fn reset(&mut self) {
+        *self.state.get_mut() = State::new();
+        *self.summary.get_mut() = ChannelState::Empty as u8;
+    }
+
+    /// Returns the allocation to its pool, if any.
     ///
-    /// If the receiving end of the channel has been dropped then an `Err` is
-    /// returned with the value that was provided.
-    pub fn send(self, value: T) -> Result<(), SendError<T>> {
-        let Self(mutex, condvar) = &self;
+    /// This must only be called once both halves have been dropped.
+    fn recycle(self: &Arc<Self>) {
+        // Drop an unreceived value now rather than when the allocation is
+        // next reused.
+        drop(self.state.lock().value.take());
+
+        if let Some(pool) = self.pool.as_ref().and_then(Weak::upgrade) {
+            pool.lock().push(Arc::clone(self));
+        }
+    }
+
+    fn send(&self, value: T) -> Result<(), SendError<T>> {
+        self.send_until(value, None)
+    }
+
+    /// Sends a value, waiting for a rendezvous until `deadline`, or without a
+    /// time limit if there is none.
+    fn send_until(&self, value: T, deadline: Option<Instant>) -> Result<(), SendError<T>> {
+        let mut state = self.state.lock();
+
+        // A rendezvous hands off only once the receiver has parked.
+        if state.rendezvous {
+            while !state.waiting && state.receiver && !state.sent {
+                match deadline {
+                    Some(deadline) if Instant::now() >= deadline => {
+                        return Err(SendError::TimedOut(value));
+                    }
+                    Some(deadline) => {
+                        let _ = self.condvar.wait_until(&mut state, deadline);
+                    }
+                    None => self.condvar.wait(&mut state),
+                }
+            }
+        }
+
+        self.deliver(&mut state, value)
+    }
+
+    /// Places a value in the slot without waiting for a rendezvous.
+    fn deliver(&self, state: &mut State<T, P>, value: T) -> Result<(), SendError<T>> {
+        if state.sent {
+            return Err(SendError::AlreadySent(value));
+        }
 
-        if self.is_disconnected() {
+        if !state.receiver {
             return Err(SendError::Disconnected(value));
         }
 
-        *mutex.lock() = Some(value);
-        let _ = condvar.notify_one();
+        state.value = Some(value);
+        state.sent = true;
+        state.sent_at = Some(Instant::now());
+        self.publish(state);
+        self.wake(state);
 
         Ok(())
     }
 
-    /// Returns `true` if this `Sender` is disconnected.
-    pub fn is_disconnected(&self) -> bool {
-        let Self(mutex, condvar) = &self;
+    /// Takes the value out of the slot, if present, and notifies the sender.
+    fn take_value(&self, state: &mut State<T, P>) -> Option<T> {
+        let value = state.value.take();
+
+        if value.is_some() {
+            self.publish(state);
+            #[cfg(feature = "async")]
+            state.wake_sender();
+        }
 
-        Arc::strong_count(mutex) == 1 || Arc::strong_count(condvar) == 1
+        value
     }
-}
 
-impl<T> Drop for Sender<T> {
-    fn drop(&mut self) {
-        let Self(_, condvar) = self;
+    /// Mirrors the locked `state` into the lock-free summary.
+    fn publish(&self, state: &State<T, P>) {
+        let summary = if state.value.is_some() {
+            ChannelState::Filled
+        } else if state.senders == 0 || state.sent || !state.receiver {
+            ChannelState::Disconnected
+        } else {
+            ChannelState::Empty
+        };
 
-        let _ = condvar.notify_one();
+        self.summary.store(summary as u8, Ordering::Release);
     }
-}
-
-/// The error returned by [`Sender::send`].
-///
-/// [`Sender::send`]: struct.Sender.html#method.send
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-pub enum SendError<T> {
-    Disconnected(T),
-}
 
-impl<T> SendError<T> {
-    /// Consumes this error and unwraps the inner value.
-    pub fn into_inner(self) -> T {
-        match self {
-            SendError::Disconnected(value) => value,
+    fn state(&self) -> ChannelState {
+        match self.summary.load(Ordering::Acquire) {
+            0 => ChannelState::Empty,
+            1 => ChannelState::Filled,
+            _ => ChannelState::Disconnected,
         }
     }
-}
 
-/// The receiving half of a one-shot channel.
-///
-/// Receivers are created by the [`channel`] function.
-///
-/// [`channel`]: fn.channel.html
-pub struct Receiver<T>(Arc<Mutex<Option<T>>>, Arc<Condvar>);
+    fn wake(&self, state: &State<T, P>) {
+        if state.waiting {
+            let _ = self.condvar.notify_one();
+        }
+
+        if let Some(watcher) = &state.watcher {
+            watcher.fire();
+        }
+
+        #[cfg(feature = "async")]
+        {
+            if let Some(waker) = &state.receiver_waker {
+                waker.wake_by_ref();
+            }
+        }
+    }
 
-impl<T> Receiver<T> {
-    fn _recv<E, F>(self, disconnect_err: E, cond_fn: F) -> Result<T, E>
+    /// Receives a value, parking with `cond_fn` while there is none.
+    ///
+    /// `cond_fn` returns `true` if it was woken by a notification rather than
+    /// a timeout.
+    fn _recv<E, F>(&self, disconnect_err: E, interrupt_err: E, cond_fn: F) -> Result<T, E>
     where
-        F: Fn(&mut MutexGuard<'_, Option<T>>, &Arc<Condvar>) -> Result<(), E>,
+        F: Fn(&mut MutexGuard<'_, State<T, P>>, &Condvar) -> Result<bool, E>,
     {
-        let Self(mut mutex, condvar) = self;
+        let mut state = self.state.lock();
 
         loop {
-            match Arc::try_unwrap(mutex) {
-                Ok(mutex) => {
-                    return mutex.into_inner().ok_or(disconnect_err);
-                }
-                Err(mutex_) => {
-                    mutex = mutex_;
-                    cond_fn(&mut mutex.lock(), &condvar)?;
+            if let Some(value) = self.take_value(&mut state) {
+                return Ok(value);
+            }
+
+            // Once the value has been taken no other value can arrive.
+            if state.senders == 0 || state.sent {
+                return Err(disconnect_err);
+            }
+
+            if state.interrupted {
+                return Err(interrupt_err);
+            }
+
+            state.waiting = true;
+
+            if state.rendezvous {
+                let _ = self.condvar.notify_all();
+                #[cfg(feature = "async")]
+                state.wake_sender();
+            }
+
+            let woken = cond_fn(&mut state, &self.condvar);
+            state.waiting = false;
+
+            #[cfg(feature = "debug-wakeups")]
+            {
+                if let Ok(true) = woken {
+                    if state.value.is_none() && state.senders > 0 && !state.interrupted {
+                        state.spurious_wakeups += 1;
+                    }
                 }
             }
+
+            woken?;
         }
     }
-}
 
-impl<T> Receiver<T> {
-    /// Blocks the current thread until a value is received or the channel is
-    /// disconnected.
-    pub fn recv(self) -> Result<T, RecvError> {
-        self._recv(RecvError::Disconnected, |mut guard, condvar| {
-            condvar.wait(&mut guard);
-            Ok(())
-        })
+    fn recv(&self) -> Result<T, RecvError> {
+        self._recv(
+            RecvError::Disconnected,
+            RecvError::Interrupted,
+            |guard, condvar| {
+                condvar.wait(guard);
+                Ok(true)
+            },
+        )
     }
 
-    /// Blocks the current thread until a value is received, but only for a
-    /// limited time.
-    ///
-    /// If the channel is disconnected before the timeout has elapsed then this
-    /// method will wake up and return an `Err`.
-    pub fn recv_timeout(self, timeout: Duration) -> Result<T, RecvTimeoutError> {
-        self._recv(RecvTimeoutError::Disconnected, move |mut guard, condvar| {
-            if condvar.wait_for(&mut guard, timeout).timed_out() {
-                Err(RecvTimeoutError::TimedOut)
-            } else {
-                Ok(())
-            }
-        })
+    fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        self.recv_timeout_with_clock(timeout, &SystemClock)
     }
 
-    /// Returns `true` if this receiver is disconnected.
-    pub fn is_disconnected(&self) -> bool {
-        let Self(mutex, condvar) = self;
+    fn recv_timeout_with_clock<C>(
+        &self,
+        timeout: Duration,
+        clock: &C,
+    ) -> Result<T, RecvTimeoutError>
+    where
+        C: Clock + ?Sized,
+    {
+        // A timeout too long to be represented never expires.
+        let deadline = clock.now().checked_add(timeout);
+
+        self._recv(
+            RecvTimeoutError::Disconnected,
+            RecvTimeoutError::Interrupted,
+            move |guard, condvar| {
+                let deadline = match deadline {
+                    Some(deadline) => deadline,
+                    None => {
+                        condvar.wait(guard);
+                        return Ok(true);
+                    }
+                };
+
+                let now = clock.now();
+
+                if now >= deadline {
+                    return Err(RecvTimeoutError::TimedOut);
+                }
+
+                let result = condvar.wait_for(guard, deadline - now);
 
-        Arc::strong_count(mutex) == 1 || Arc::strong_count(condvar) == 1
+                Ok(!result.timed_out())
+            },
+        )
     }
-}
 
-/// The error returned by [`Receiver::recv`].
-///
-/// [`Receiver::recv`]: struct.Receiver.html#method.recv
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-pub enum RecvError {
-    Disconnected,
+    /// Returns `true` if both halves have now been dropped.
+    fn drop_sender(&self) -> bool {
+        let mut state = self.state.lock();
+        state.senders -= 1;
+
+        if state.senders == 0 {
+            self.publish(&state);
+            self.wake(&state);
+        }
+
+        state.senders == 0 && !state.receiver
+    }
+
+    /// Returns `true` if both halves have now been dropped.
+    fn drop_receiver(&self) -> bool {
+        let mut state = self.state.lock();
+        state.receiver = false;
+        self.publish(&state);
+        #[cfg(feature = "async")]
+        state.wake_sender();
+
+        if state.rendezvous {
+            let _ = self.condvar.notify_all();
+        }
+
+        state.senders == 0
+    }
 }
 
-/// The error returned by [`Receiver::recv_timeout`].
+/// The sending half of a one-shot channel.
+///
+/// Senders are created by the [`channel`] function.
+///
+/// [`channel`]: fn.channel.html
+#[derive(Debug)]
+pub struct Sender<T, P = ()>(Arc<Inner<T, P>>);
+
+impl<T, P> Sender<T, P> {
+    /// Sends a value through the one-shot channel.
+    ///
+    /// If the receiving end of the channel has been dropped, or another
+    /// sender has already sent a value, then an `Err` is returned with the
+    /// value that was provided.
+    pub fn send(self, value: T) -> Result<(), SendError<T>> {
+        let Self(inner) = &self;
+
+        inner.send(value)
+    }
+
+    /// Sends a value through the one-shot channel, waiting at most `timeout`
+    /// for the receiver of a rendezvous channel to park.
+    ///
+    /// If the receiver has not parked in a blocking receive before the
+    /// timeout elapses then the value is returned in
+    /// [`SendError::TimedOut`]. If the receiver is dropped during the wait
+    /// then the sender is woken immediately and the value is returned in
+    /// [`SendError::Disconnected`].
+    ///
+    /// Channels which are not rendezvous channels never wait, so this
+    /// behaves exactly like [`send`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use nano_oneshot::SendError;
+    ///
+    /// let (s, _r) = ::nano_oneshot::channel_rendezvous();
+    ///
+    /// assert_eq!(
+    ///     s.send_timeout(1, Duration::from_millis(10)),
+    ///     Err(SendError::TimedOut(1)),
+    /// );
+    /// ```
+    ///
+    /// [`SendError::TimedOut`]: enum.SendError.html#variant.TimedOut
+    /// [`SendError::Disconnected`]: enum.SendError.html#variant.Disconnected
+    /// [`send`]: #method.send
+    pub fn send_timeout(self, value: T, timeout: Duration) -> Result<(), SendError<T>> {
+        let Self(inner) = &self;
+
+        inner.send_until(value, Instant::now().checked_add(timeout))
+    }
+
+    /// Sends a value through the one-shot channel without consuming the
+    /// sender.
+    ///
+    /// If the receiving end of the channel has been dropped, or a value has
+    /// already been sent, then the value that was provided is returned in the
+    /// `Err`. The sender is kept so that the caller may retry.
+    pub fn send_back(&self, value: T) -> Result<(), T> {
+        let Self(inner) = self;
+
+        inner.send(value).map_err(SendError::into_inner)
+    }
+
+    /// Returns `true` if this `Sender` is disconnected.
+    pub fn is_disconnected(&self) -> bool {
+        let Self(inner) = self;
+
+        !inner.state.lock().receiver
+    }
+
+    /// Returns a snapshot of the channel state without taking the lock.
+    ///
+    /// The snapshot is read with a single atomic load, making it cheap to
+    /// poll across many channels. It is updated alongside the slot, but may
+    /// be momentarily stale: a send or disconnect racing with this call may
+    /// not yet be reflected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_oneshot::ChannelState;
+    ///
+    /// let (s, r) = ::nano_oneshot::channel::<i32>();
+    /// assert_eq!(s.state(), ChannelState::Empty);
+    ///
+    /// drop(r);
+    /// assert_eq!(s.state(), ChannelState::Disconnected);
+    /// ```
+    pub fn state(&self) -> ChannelState {
+        let Self(inner) = self;
+
+        inner.state()
+    }
+
+    /// Returns the identifier shared by both halves of the channel.
+    ///
+    /// See [`channel_with_id`].
+    ///
+    /// [`channel_with_id`]: fn.channel_with_id.html
+    pub fn id(&self) -> u64 {
+        let Self(inner) = self;
+
+        inner.id
+    }
+
+    /// Wakes a blocked receiver without sending a value, for testing the
+    /// spurious wakeup count.
+    #[cfg(feature = "debug-wakeups")]
+    #[doc(hidden)]
+    pub fn __wake_receiver(&self) {
+        let Self(inner) = self;
+
+        let state = inner.state.lock();
+
+        if state.waiting {
+            let _ = inner.condvar.notify_all();
+        }
+    }
+
+    /// Sends a value on this channel, returning a future which resolves once
+    /// the receiver has taken the value.
+    ///
+    /// **Note**: Requires the feature `async`.
+    ///
+    /// The value is sent when the future is first polled. If the receiver is
+    /// dropped before taking the value then the value is returned in
+    /// [`SendError::Disconnected`].
+    ///
+    /// On a channel created by [`channel_rendezvous`] the value is held by
+    /// the future, without blocking, until the receiver is waiting for it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::future::Future;
+    /// use std::task::{Context, Poll, Waker};
+    ///
+    /// let (s, r) = ::nano_oneshot::channel();
+    ///
+    /// let mut cx = Context::from_waker(Waker::noop());
+    /// let mut fut = Box::pin(s.send_async(1));
+    ///
+    /// assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+    ///
+    /// assert_eq!(r.recv(), Ok(1));
+    /// assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(Ok(())));
+    /// ```
+    ///
+    /// [`SendError::Disconnected`]: enum.SendError.html#variant.Disconnected
+    /// [`channel_rendezvous`]: fn.channel_rendezvous.html
+    #[cfg(feature = "async")]
+    pub fn send_async(self, value: T) -> impl Future<Output = Result<(), SendError<T>>> {
+        SendAsync {
+            sender: self,
+            value: Some(value),
+        }
+    }
+
+    /// Returns `true` if the receiver is currently blocked waiting for the
+    /// value.
+    ///
+    /// This is a scheduling hint only: the receiver may park or wake
+    /// immediately after this returns, so the answer may already be stale.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::thread;
+    /// use std::time::Duration;
+    ///
+    /// let (s, r) = ::nano_oneshot::channel();
+    /// assert!(!s.receiver_is_waiting());
+    ///
+    /// let handle = thread::spawn(move || r.recv());
+    ///
+    /// while !s.receiver_is_waiting() {
+    ///     thread::sleep(Duration::from_millis(1));
+    /// }
+    ///
+    /// let _ = s.send(1);
+    /// assert_eq!(handle.join().unwrap(), Ok(1));
+    /// ```
+    pub fn receiver_is_waiting(&self) -> bool {
+        let Self(inner) = self;
+
+        inner.state.lock().waiting
+    }
+
+    /// Publishes a progress update, replacing any previous update.
+    ///
+    /// Progress updates do not wake a receiver blocked on the final value.
+    pub fn progress(&self, progress: P) {
+        let Self(inner) = self;
+
+        inner.state.lock().progress = Some(progress);
+    }
+}
+
+impl<T, P> Drop for Sender<T, P> {
+    fn drop(&mut self) {
+        let Self(inner) = self;
+
+        if inner.drop_sender() {
+            inner.recycle();
+        }
+    }
+}
+
+/// The error returned by [`Sender::send`].
+///
+/// [`Sender::send`]: struct.Sender.html#method.send
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SendError<T> {
+    Disconnected(T),
+    /// Another sender sharing the channel has already sent a value.
+    AlreadySent(T),
+    /// The receiver of a rendezvous channel did not park before the timeout
+    /// elapsed.
+    ///
+    /// See [`Sender::send_timeout`].
+    ///
+    /// [`Sender::send_timeout`]: struct.Sender.html#method.send_timeout
+    TimedOut(T),
+}
+
+impl<T> SendError<T> {
+    /// Consumes this error and unwraps the inner value.
+    pub fn into_inner(self) -> T {
+        match self {
+            SendError::Disconnected(value)
+            | SendError::AlreadySent(value)
+            | SendError::TimedOut(value) => value,
+        }
+    }
+}
+
+/// The receiving half of a one-shot channel.
+///
+/// Receivers are created by the [`channel`] function.
+///
+/// [`channel`]: fn.channel.html
+pub struct Receiver<T, P = ()>(Arc<Inner<T, P>>, Option<T>);
+
+impl<T, P> Receiver<T, P> {
+    /// Blocks the current thread until a value is received or the channel is
+    /// disconnected.
+    pub fn recv(mut self) -> Result<T, RecvError> {
+        let Self(inner, peeked) = &mut self;
+
+        match peeked.take() {
+            Some(value) => Ok(value),
+            None => inner.recv(),
+        }
+    }
+
+    /// Blocks the current thread until a value is received or the channel is
+    /// disconnected, also returning a [`DeliveryToken`] for the value.
+    ///
+    /// This behaves exactly like [`recv`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (s, r) = ::nano_oneshot::channel_with_id(7);
+    ///
+    /// let _ = s.send("hello");
+    /// let (value, token) = r.recv_with_token().unwrap();
+    ///
+    /// assert_eq!(value, "hello");
+    /// assert_eq!(token.id(), 7);
+    /// println!("delivered after {:?}", token.sent_at().elapsed());
+    /// ```
+    ///
+    /// [`DeliveryToken`]: struct.DeliveryToken.html
+    /// [`recv`]: #method.recv
+    pub fn recv_with_token(self) -> Result<(T, DeliveryToken), RecvError> {
+        let inner = Arc::clone(&self.0);
+        let value = self.recv()?;
+
+        let token = DeliveryToken {
+            id: inner.id,
+            sent_at: inner.state.lock().sent_at.expect("value was sent"),
+        };
+
+        Ok((value, token))
+    }
+
+    /// Blocks the current thread until a value is received or the channel is
+    /// disconnected, also returning the time spent blocked.
+    ///
+    /// This behaves exactly like [`recv`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (s, r) = ::nano_oneshot::channel();
+    ///
+    /// let _ = s.send(1);
+    /// let (result, waited) = r.recv_timed();
+    ///
+    /// assert_eq!(result, Ok(1));
+    /// println!("waited {:?}", waited);
+    /// ```
+    ///
+    /// [`recv`]: #method.recv
+    pub fn recv_timed(self) -> (Result<T, RecvError>, Duration) {
+        let start = Instant::now();
+        let result = self.recv();
+
+        (result, start.elapsed())
+    }
+
+    /// Blocks the current thread until a value is received, but only for a
+    /// limited time.
+    ///
+    /// If the channel is disconnected before the timeout has elapsed then this
+    /// method will wake up and return an `Err`.
+    pub fn recv_timeout(mut self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        let Self(inner, peeked) = &mut self;
+
+        match peeked.take() {
+            Some(value) => Ok(value),
+            None => inner.recv_timeout(timeout),
+        }
+    }
+
+    /// Blocks the current thread until a value is received, but only for a
+    /// limited time as measured by `clock`.
+    ///
+    /// The deadline and every expiry check are computed from [`Clock::now`],
+    /// so a mock clock can be used to exercise timeouts deterministically.
+    /// The receiver still parks on the channel for the remaining time as
+    /// reported by the clock, after which the clock is checked again.
+    ///
+    /// [`recv_timeout`] is equivalent to calling this with [`SystemClock`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    /// use std::time::{Duration, Instant};
+    /// use nano_oneshot::{Clock, RecvTimeoutError};
+    ///
+    /// // A clock which jumps forward an hour every time it is read.
+    /// struct Skipping(Cell<Instant>);
+    ///
+    /// impl Clock for Skipping {
+    ///     fn now(&self) -> Instant {
+    ///         let now = self.0.get();
+    ///         self.0.set(now + Duration::from_secs(3600));
+    ///         now
+    ///     }
+    /// }
+    ///
+    /// let (_s, r) = ::nano_oneshot::channel::<i32>();
+    /// let clock = Skipping(Cell::new(Instant::now()));
+    ///
+    /// let timeout = Duration::from_secs(3600);
+    /// assert_eq!(r.recv_timeout_with_clock(timeout, &clock), Err(RecvTimeoutError::TimedOut));
+    /// ```
+    ///
+    /// [`Clock::now`]: trait.Clock.html#tymethod.now
+    /// [`recv_timeout`]: #method.recv_timeout
+    /// [`SystemClock`]: struct.SystemClock.html
+    pub fn recv_timeout_with_clock<C>(
+        mut self,
+        timeout: Duration,
+        clock: &C,
+    ) -> Result<T, RecvTimeoutError>
+    where
+        C: Clock + ?Sized,
+    {
+        let Self(inner, peeked) = &mut self;
+
+        match peeked.take() {
+            Some(value) => Ok(value),
+            None => inner.recv_timeout_with_clock(timeout, clock),
+        }
+    }
+
+    /// Blocks the current thread until a value is received, returning a
+    /// reference to it without consuming it.
+    ///
+    /// The value stays with the receiver until it is committed with [`take`],
+    /// or received by any of the consuming methods. Repeated calls return the
+    /// same value.
+    ///
+    /// If the value has already been taken then this returns an `Err`, as no
+    /// further value can arrive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (s, mut r) = ::nano_oneshot::channel();
+    ///
+    /// let _ = s.send(128);
+    /// assert_eq!(r.recv_peeking(), Ok(&128));
+    ///
+    /// assert_eq!(r.take(), Some(128));
+    /// assert!(r.recv_peeking().is_err());
+    /// ```
+    ///
+    /// [`take`]: #method.take
+    pub fn recv_peeking(&mut self) -> Result<&T, RecvError> {
+        let Self(inner, peeked) = self;
+
+        if peeked.is_none() {
+            *peeked = Some(inner.recv()?);
+        }
+
+        Ok(peeked.as_ref().expect("value is present"))
+    }
+
+    /// Takes the value out of the receiver without blocking.
+    ///
+    /// This returns a value previously returned by reference from
+    /// [`recv_peeking`], or a value which has been sent but not yet received.
+    /// Otherwise `None` is returned.
+    ///
+    /// [`recv_peeking`]: #method.recv_peeking
+    pub fn take(&mut self) -> Option<T> {
+        let Self(inner, peeked) = self;
+
+        peeked
+            .take()
+            .or_else(|| inner.take_value(&mut inner.state.lock()))
+    }
+
+    /// Blocks the current thread until a value is received, then writes it
+    /// into `out`.
+    ///
+    /// This allows a large value to be placed into storage owned by the
+    /// caller. If the channel is disconnected then `out` is set to `None` and
+    /// an `Err` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (s, r) = ::nano_oneshot::channel();
+    /// let mut out = None;
+    ///
+    /// let _ = s.send([0u8; 4096]);
+    /// r.recv_into(&mut out).unwrap();
+    ///
+    /// assert_eq!(out, Some([0; 4096]));
+    /// ```
+    pub fn recv_into(self, out: &mut Option<T>) -> Result<(), RecvError> {
+        *out = None;
+        *out = Some(self.recv()?);
+
+        Ok(())
+    }
+
+    /// Blocks the current thread until a value is received, returning
+    /// `fallback` if the channel is disconnected.
+    pub fn recv_or(self, fallback: T) -> T {
+        self.recv().unwrap_or(fallback)
+    }
+
+    /// Blocks the current thread until a value is received, returning the
+    /// result of `f` if the channel is disconnected.
+    ///
+    /// Unlike [`recv_or`] the fallback is only computed if it is needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (s, r) = ::nano_oneshot::channel::<Vec<u8>>();
+    /// drop(s);
+    ///
+    /// assert_eq!(r.recv_or_else(|| vec![0; 16]).len(), 16);
+    /// ```
+    ///
+    /// [`recv_or`]: #method.recv_or
+    pub fn recv_or_else<F>(self, f: F) -> T
+    where
+        F: FnOnce() -> T,
+    {
+        self.recv().unwrap_or_else(|_| f())
+    }
+
+    /// Blocks the current thread until a value is received, returning the
+    /// default value of `T` if the channel is disconnected.
+    pub fn recv_or_default(self) -> T
+    where
+        T: Default,
+    {
+        self.recv().unwrap_or_default()
+    }
+
+    /// Blocks the current thread until a value is received, then forwards it
+    /// into a [`std::sync::mpsc`] channel.
+    ///
+    /// Nothing is forwarded if this channel is disconnected, and the value is
+    /// discarded if the `mpsc` channel is disconnected.
+    ///
+    /// [`std::sync::mpsc`]: https://doc.rust-lang.org/std/sync/mpsc/index.html
+    pub fn forward_to(self, tx: mpsc::Sender<T>) {
+        if let Ok(value) = self.recv() {
+            let _ = tx.send(value);
+        }
+    }
+
+    /// Spawns a thread which forwards the received value into a
+    /// [`std::sync::mpsc`] channel.
+    ///
+    /// See [`forward_to`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::mpsc;
+    ///
+    /// let (s, r) = ::nano_oneshot::channel();
+    /// let (tx, rx) = mpsc::channel();
+    ///
+    /// r.forward_on_thread(tx);
+    /// let _ = s.send("hello");
+    ///
+    /// assert_eq!(rx.recv().unwrap(), "hello");
+    /// ```
+    ///
+    /// [`std::sync::mpsc`]: https://doc.rust-lang.org/std/sync/mpsc/index.html
+    /// [`forward_to`]: #method.forward_to
+    pub fn forward_on_thread(self, tx: mpsc::Sender<T>) -> JoinHandle<()>
+    where
+        T: Send + 'static,
+        P: Send + 'static,
+    {
+        thread::spawn(move || self.forward_to(tx))
+    }
+
+    /// Spawns a thread which applies `f` to the received value, returning a
+    /// receiver for the result.
+    ///
+    /// If this channel is disconnected then `f` is not run and the returned
+    /// receiver is disconnected too. The returned receiver is also
+    /// disconnected if `f` panics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (s, r) = ::nano_oneshot::channel();
+    ///
+    /// let r = r.then_spawn(|n: i32| n * 2).then_spawn(|n| n.to_string());
+    /// let _ = s.send(21);
+    ///
+    /// assert_eq!(r.recv().unwrap(), "42");
+    /// ```
+    pub fn then_spawn<U, F>(self, f: F) -> Receiver<U>
+    where
+        T: Send + 'static,
+        P: Send + 'static,
+        U: Send + 'static,
+        F: FnOnce(T) -> U + Send + 'static,
+    {
+        let (s, r) = channel();
+
+        thread::spawn(move || {
+            if let Ok(value) = self.recv() {
+                let _ = s.send(f(value));
+            }
+        });
+
+        r
+    }
+
+    /// Returns the identifier shared by both halves of the channel.
+    ///
+    /// See [`channel_with_id`].
+    ///
+    /// [`channel_with_id`]: fn.channel_with_id.html
+    pub fn id(&self) -> u64 {
+        let Self(inner, _) = self;
+
+        inner.id
+    }
+
+    /// Returns the number of spurious wakeups of blocking receives on this
+    /// channel.
+    ///
+    /// A wakeup is spurious if the receiver was woken by the condition
+    /// variable while there was still no value, the channel was still
+    /// connected and it had not been interrupted. Timeouts are not counted.
+    /// Each is followed by rechecking the channel and parking again.
+    ///
+    /// The count can be read after blocking with [`recv_peeking`], which
+    /// keeps the receiver. The consuming receives can use
+    /// [`recv_with_wakeups`] instead.
+    ///
+    /// **Note**: Requires the feature `debug-wakeups`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::thread;
+    ///
+    /// let (s, mut r) = ::nano_oneshot::channel();
+    ///
+    /// thread::spawn(move || s.send(1));
+    /// assert_eq!(r.recv_peeking(), Ok(&1));
+    ///
+    /// println!("{} spurious wakeups", r.spurious_wakeups());
+    /// ```
+    ///
+    /// [`recv_peeking`]: #method.recv_peeking
+    /// [`recv_with_wakeups`]: #method.recv_with_wakeups
+    #[cfg(feature = "debug-wakeups")]
+    pub fn spurious_wakeups(&self) -> usize {
+        let Self(inner, _) = self;
+
+        inner.state.lock().spurious_wakeups
+    }
+
+    /// Blocks the current thread until a value is received or the channel is
+    /// disconnected, also returning the number of spurious wakeups.
+    ///
+    /// This behaves exactly like [`recv`]. See [`spurious_wakeups`].
+    ///
+    /// **Note**: Requires the feature `debug-wakeups`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (s, r) = ::nano_oneshot::channel();
+    ///
+    /// let _ = s.send(1);
+    /// let (result, wakeups) = r.recv_with_wakeups();
+    ///
+    /// assert_eq!(result, Ok(1));
+    /// assert_eq!(wakeups, 0);
+    /// ```
+    ///
+    /// [`recv`]: #method.recv
+    /// [`spurious_wakeups`]: #method.spurious_wakeups
+    #[cfg(feature = "debug-wakeups")]
+    pub fn recv_with_wakeups(self) -> (Result<T, RecvError>, usize) {
+        let inner = Arc::clone(&self.0);
+        let result = self.recv();
+        let wakeups = inner.state.lock().spurious_wakeups;
+
+        (result, wakeups)
+    }
+
+    /// Returns a snapshot of the channel state without taking the lock.
+    ///
+    /// The snapshot is read with a single atomic load, making it cheap to
+    /// poll across many channels. It is updated alongside the slot, but may
+    /// be momentarily stale: a send or disconnect racing with this call may
+    /// not yet be reflected. Use [`poll_state`] for an exact answer.
+    ///
+    /// A value held by [`recv_peeking`] is always reported as
+    /// [`ChannelState::Filled`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_oneshot::ChannelState;
+    ///
+    /// let (s, r) = ::nano_oneshot::channel();
+    /// assert_eq!(r.state(), ChannelState::Empty);
+    ///
+    /// let _ = s.send(1);
+    /// assert_eq!(r.state(), ChannelState::Filled);
+    /// ```
+    ///
+    /// [`poll_state`]: #method.poll_state
+    /// [`recv_peeking`]: #method.recv_peeking
+    /// [`ChannelState::Filled`]: enum.ChannelState.html#variant.Filled
+    pub fn state(&self) -> ChannelState {
+        let Self(inner, peeked) = self;
+
+        if peeked.is_some() {
+            ChannelState::Filled
+        } else {
+            inner.state()
+        }
+    }
+
+    /// Returns `true` if this receiver is disconnected.
+    pub fn is_disconnected(&self) -> bool {
+        let Self(inner, _) = self;
+
+        inner.state.lock().senders == 0
+    }
+
+    /// Attempts to receive the value without blocking.
+    ///
+    /// Returns `None` while the sender is connected and has not yet sent a
+    /// value, otherwise returns the outcome of the receive. Unlike [`recv`]
+    /// the receiver is not consumed, so this may be called repeatedly from a
+    /// custom polling loop.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (s, mut r) = ::nano_oneshot::channel();
+    ///
+    /// assert_eq!(r.recv_now(), None);
+    ///
+    /// let _ = s.send(128);
+    /// assert_eq!(r.recv_now(), Some(Ok(128)));
+    /// ```
+    ///
+    /// [`recv`]: #method.recv
+    pub fn recv_now(&mut self) -> Option<Result<T, RecvError>> {
+        let Self(inner, peeked) = self;
+
+        if let Some(value) = peeked.take() {
+            return Some(Ok(value));
+        }
+
+        let mut state = inner.state.lock();
+
+        match inner.take_value(&mut state) {
+            Some(value) => Some(Ok(value)),
+            // Once the value has been taken no other value can arrive.
+            None if state.senders == 0 || state.sent => Some(Err(RecvError::Disconnected)),
+            None => None,
+        }
+    }
+
+    /// Attempts to receive the value without blocking, returning the receiver
+    /// if no value is available.
+    ///
+    /// This is a by-value alternative to [`recv_now`], for callers which
+    /// store the receiver between polls and would rather reassign it than
+    /// hold a mutable borrow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_oneshot::TryRecvError;
+    ///
+    /// let (s, r) = ::nano_oneshot::channel();
+    ///
+    /// let r = match r.try_recv_owned() {
+    ///     Err((r, TryRecvError::Empty)) => r,
+    ///     _ => unreachable!(),
+    /// };
+    ///
+    /// let _ = s.send(1);
+    /// assert_eq!(r.try_recv_owned().ok(), Some(1));
+    /// ```
+    ///
+    /// [`recv_now`]: #method.recv_now
+    pub fn try_recv_owned(mut self) -> Result<T, (Self, TryRecvError)> {
+        match self.recv_now() {
+            Some(Ok(value)) => Ok(value),
+            Some(Err(_)) => Err((self, TryRecvError::Disconnected)),
+            None => Err((self, TryRecvError::Empty)),
+        }
+    }
+
+    /// Converts this receiver into an iterator which yields the outcome of a
+    /// single blocking receive.
+    ///
+    /// Exactly one item is yielded: `Ok` with the value, or `Err` if the
+    /// channel was disconnected. The receive happens when the item is first
+    /// requested.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_oneshot::RecvError;
+    ///
+    /// let (s1, r1) = ::nano_oneshot::channel();
+    /// let (s2, r2) = ::nano_oneshot::channel::<i32>();
+    ///
+    /// let _ = s1.send(128);
+    /// drop(s2);
+    ///
+    /// let values: Result<Vec<_>, _> = r1.into_result_iter().chain(r2.into_result_iter()).collect();
+    /// assert_eq!(values, Err(RecvError::Disconnected));
+    /// ```
+    pub fn into_result_iter(self) -> impl Iterator<Item = Result<T, RecvError>> {
+        iter::once_with(move || self.recv())
+    }
+
+    /// Wraps this receiver in a guard which reports a value that was sent but
+    /// never received.
+    ///
+    /// By default dropping the guard panics if a value is waiting in the
+    /// channel. Use [`MustRecv::on_unread`] to report it some other way.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// let (s, r) = ::nano_oneshot::channel();
+    /// let r = r.must_use();
+    ///
+    /// let _ = s.send("nobody reads this");
+    /// drop(r);
+    /// ```
+    ///
+    /// [`MustRecv::on_unread`]: struct.MustRecv.html#method.on_unread
+    pub fn must_use(self) -> MustRecv<T, P> {
+        MustRecv {
+            receiver: Some(self),
+            on_unread: None,
+        }
+    }
+
+    /// Returns the latest progress update published by the sender, if any.
+    ///
+    /// The update is cloned and left in place, so repeated calls return the
+    /// same update until it is replaced.
+    pub fn poll_progress(&self) -> Option<P>
+    where
+        P: Clone,
+    {
+        let Self(inner, _) = self;
+
+        inner.state.lock().progress.clone()
+    }
+
+    /// Returns the current state of the channel without receiving the value.
+    ///
+    /// The state is read under a single lock acquisition, so a channel with a
+    /// value that was sent before the sender was dropped is reported as
+    /// [`RecvState::Ready`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nano_oneshot::RecvState;
+    ///
+    /// let (s, r) = ::nano_oneshot::channel();
+    /// assert_eq!(r.poll_state(), RecvState::Empty);
+    ///
+    /// let _ = s.send(128);
+    /// assert_eq!(r.poll_state(), RecvState::Ready);
+    /// ```
+    ///
+    /// [`RecvState::Ready`]: enum.RecvState.html#variant.Ready
+    pub fn poll_state(&self) -> RecvState {
+        let Self(inner, peeked) = self;
+        let state = inner.state.lock();
+
+        if peeked.is_some() || state.value.is_some() {
+            RecvState::Ready
+        } else if state.senders == 0 || state.sent {
+            RecvState::Disconnected
+        } else {
+            RecvState::Empty
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T, P> Receiver<T, P> {
+    /// Attempts to receive the value, registering the current task to be
+    /// woken if it is not yet available.
+    ///
+    /// **Note**: Requires the feature `async`.
+    ///
+    /// This is cancel-safe: the value is only taken out of the channel when
+    /// it is returned in `Poll::Ready`, so if polling is abandoned the value
+    /// remains available to any later receive. The same holds for `.await`ing
+    /// a `&mut Receiver`, e.g. in a `select!` loop.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::task::{Context, Poll, Waker};
+    ///
+    /// let (s, mut r) = ::nano_oneshot::channel();
+    /// let mut cx = Context::from_waker(Waker::noop());
+    ///
+    /// assert_eq!(r.poll_recv(&mut cx), Poll::Pending);
+    ///
+    /// let _ = s.send(1);
+    /// assert_eq!(r.poll_recv(&mut cx), Poll::Ready(Ok(1)));
+    /// ```
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Result<T, RecvError>> {
+        let Self(inner, peeked) = self;
+
+        if let Some(value) = peeked.take() {
+            return Poll::Ready(Ok(value));
+        }
+
+        let mut state = inner.state.lock();
+
+        if let Some(value) = inner.take_value(&mut state) {
+            return Poll::Ready(Ok(value));
+        }
+
+        if state.senders == 0 || state.sent {
+            return Poll::Ready(Err(RecvError::Disconnected));
+        }
+
+        if state.interrupted {
+            return Poll::Ready(Err(RecvError::Interrupted));
+        }
+
+        state.receiver_waker = Some(cx.waker().clone());
+
+        Poll::Pending
+    }
+}
+
+// The peeked value is never pinned, so the receiver may be moved freely.
+#[cfg(feature = "async")]
+impl<T, P> Unpin for Receiver<T, P> {}
+
+/// Receives the value asynchronously.
+///
+/// **Note**: Requires the feature `async`.
+///
+/// See [`Receiver::poll_recv`], including its guarantee of cancel safety.
+///
+/// [`Receiver::poll_recv`]: struct.Receiver.html#method.poll_recv
+#[cfg(feature = "async")]
+impl<T, P> Future for Receiver<T, P> {
+    type Output = Result<T, RecvError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.get_mut().poll_recv(cx)
+    }
+}
+
+impl<T, P> Drop for Receiver<T, P> {
+    fn drop(&mut self) {
+        let Self(inner, _) = self;
+
+        if inner.drop_receiver() {
+            inner.recycle();
+        }
+    }
+}
+
+/// A receiver which reports a value that was sent but never received.
+///
+/// Guards are created by [`Receiver::must_use`].
+///
+/// [`Receiver::must_use`]: struct.Receiver.html#method.must_use
+#[must_use]
+pub struct MustRecv<T, P = ()> {
+    receiver: Option<Receiver<T, P>>,
+    on_unread: Option<Box<dyn FnOnce() + Send>>,
+}
+
+impl<T, P> MustRecv<T, P> {
+    /// Calls `f` instead of panicking if the guard is dropped while a value is
+    /// waiting in the channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// let unread = Arc::new(AtomicBool::new(false));
+    /// let flag = Arc::clone(&unread);
+    ///
+    /// let (s, r) = ::nano_oneshot::channel();
+    /// let r = r.must_use().on_unread(move || flag.store(true, Ordering::SeqCst));
+    ///
+    /// let _ = s.send(128);
+    /// drop(r);
+    ///
+    /// assert!(unread.load(Ordering::SeqCst));
+    /// ```
+    pub fn on_unread<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.on_unread = Some(Box::new(f));
+        self
+    }
+
+    /// Blocks the current thread until a value is received or the channel is
+    /// disconnected.
+    ///
+    /// See [`Receiver::recv`].
+    ///
+    /// [`Receiver::recv`]: struct.Receiver.html#method.recv
+    pub fn recv(mut self) -> Result<T, RecvError> {
+        self.receiver.take().expect("receiver is present").recv()
+    }
+
+    /// Blocks the current thread until a value is received, but only for a
+    /// limited time.
+    ///
+    /// See [`Receiver::recv_timeout`].
+    ///
+    /// [`Receiver::recv_timeout`]: struct.Receiver.html#method.recv_timeout
+    pub fn recv_timeout(mut self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        self.receiver
+            .take()
+            .expect("receiver is present")
+            .recv_timeout(timeout)
+    }
+
+    /// Unwraps the receiver, disarming the guard.
+    pub fn into_inner(mut self) -> Receiver<T, P> {
+        self.receiver.take().expect("receiver is present")
+    }
+}
+
+impl<T, P> Drop for MustRecv<T, P> {
+    fn drop(&mut self) {
+        let unread = match &self.receiver {
+            Some(r) => r.poll_state() == RecvState::Ready,
+            None => false,
+        };
+
+        if !unread {
+            return;
+        }
+
+        match self.on_unread.take() {
+            Some(f) => f(),
+            None if !thread::panicking() => {
+                panic!("a one-shot value was sent but never received")
+            }
+            None => (),
+        }
+    }
+}
+
+/// A pool of one-shot channel allocations.
+///
+/// Channels created by [`OneshotPool::channel`] return their allocation to
+/// the pool once both halves have been dropped, and it is reused by a later
+/// call instead of allocating. This avoids allocating per channel when many
+/// short-lived channels are created, e.g. one per request.
+///
+/// Liveness is tracked by the halves themselves rather than by the allocation,
+/// so a reused allocation is indistinguishable from a new channel: it has a
+/// new identifier, and any value left unreceived by its previous use is
+/// dropped. An allocation is only reused once nothing else refers to it.
+///
+/// The pool retains as many allocations as were simultaneously in use, and
+/// cloning the pool shares the same allocations. Channels created by the pool
+/// remain usable after the pool has been dropped, but are then deallocated as
+/// normal.
+///
+/// # Examples
+///
+/// ```
+/// use nano_oneshot::OneshotPool;
+///
+/// let pool = OneshotPool::new();
+///
+/// for i in 0..3 {
+///     let (s, r) = pool.channel();
+///
+///     let _ = s.send(i);
+///     assert_eq!(r.recv().unwrap(), i);
+/// }
+///
+/// assert_eq!(pool.idle(), 1);
+/// ```
+///
+/// [`OneshotPool::channel`]: struct.OneshotPool.html#method.channel
+pub struct OneshotPool<T>(Arc<Recycled<T, ()>>);
+
+impl<T> OneshotPool<T> {
+    /// Creates a new, empty pool.
+    pub fn new() -> Self {
+        Self(Arc::new(Mutex::new(Vec::new())))
+    }
+
+    /// Creates a new one-shot channel, reusing a pooled allocation if one is
+    /// available.
+    ///
+    /// See [`channel`].
+    ///
+    /// [`channel`]: fn.channel.html
+    pub fn channel(&self) -> (Sender<T>, Receiver<T>) {
+        let Self(pool) = self;
+
+        let recycled = pool.lock().pop();
+
+        // The half which recycled the allocation may not have released its
+        // reference yet, in which case a fresh allocation is used instead.
+        let inner = match recycled {
+            Some(mut inner) => match Arc::get_mut(&mut inner) {
+                Some(reused) => {
+                    reused.reset();
+                    reused.id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+                    inner
+                }
+                None => self.allocate(),
+            },
+            None => self.allocate(),
+        };
+
+        let s = Sender(Arc::clone(&inner));
+        let r = Receiver(inner, None);
+
+        (s, r)
+    }
+
+    /// Returns the number of allocations waiting to be reused.
+    pub fn idle(&self) -> usize {
+        let Self(pool) = self;
+
+        pool.lock().len()
+    }
+
+    fn allocate(&self) -> Arc<Inner<T, ()>> {
+        let Self(pool) = self;
+
+        let mut inner = Inner::new();
+        inner.pool = Some(Arc::downgrade(pool));
+
+        Arc::new(inner)
+    }
+}
+
+impl<T> Clone for OneshotPool<T> {
+    fn clone(&self) -> Self {
+        let Self(pool) = self;
+
+        Self(Arc::clone(pool))
+    }
+}
+
+impl<T> Default for OneshotPool<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> ::std::fmt::Debug for OneshotPool<T> {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        f.debug_struct("OneshotPool")
+            .field("idle", &self.idle())
+            .finish()
+    }
+}
+
+/// Storage for a one-shot channel created by [`channel_in`].
+///
+/// [`channel_in`]: fn.channel_in.html
+#[derive(Debug)]
+pub struct OneshotSlot<T>(Inner<T, ()>);
+
+impl<T> OneshotSlot<T> {
+    /// Creates new storage for a one-shot channel.
+    pub fn new() -> Self {
+        Self(Inner::new())
+    }
+}
+
+impl<T> Default for OneshotSlot<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The sending half of a one-shot channel which borrows its storage.
+///
+/// Borrowed senders are created by the [`channel_in`] function.
+///
+/// [`channel_in`]: fn.channel_in.html
+#[derive(Debug)]
+pub struct BorrowedSender<'a, T>(&'a Inner<T, ()>);
+
+impl<T> BorrowedSender<'_, T> {
+    /// Sends a value through the one-shot channel.
+    ///
+    /// If the receiving end of the channel has been dropped then an `Err` is
+    /// returned with the value that was provided.
+    pub fn send(self, value: T) -> Result<(), SendError<T>> {
+        let Self(inner) = self;
+
+        inner.send(value)
+    }
+
+    /// Returns `true` if this `BorrowedSender` is disconnected.
+    pub fn is_disconnected(&self) -> bool {
+        let Self(inner) = self;
+
+        !inner.state.lock().receiver
+    }
+}
+
+impl<T> Drop for BorrowedSender<'_, T> {
+    fn drop(&mut self) {
+        let Self(inner) = self;
+
+        inner.drop_sender();
+    }
+}
+
+/// The receiving half of a one-shot channel which borrows its storage.
+///
+/// Borrowed receivers are created by the [`channel_in`] function.
+///
+/// [`channel_in`]: fn.channel_in.html
+pub struct BorrowedReceiver<'a, T>(&'a Inner<T, ()>);
+
+impl<T> BorrowedReceiver<'_, T> {
+    /// Blocks the current thread until a value is received or the channel is
+    /// disconnected.
+    pub fn recv(self) -> Result<T, RecvError> {
+        let Self(inner) = &self;
+
+        inner.recv()
+    }
+
+    /// Blocks the current thread until a value is received, but only for a
+    /// limited time.
+    ///
+    /// If the channel is disconnected before the timeout has elapsed then this
+    /// method will wake up and return an `Err`.
+    pub fn recv_timeout(self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        let Self(inner) = &self;
+
+        inner.recv_timeout(timeout)
+    }
+
+    /// Returns `true` if this receiver is disconnected.
+    pub fn is_disconnected(&self) -> bool {
+        let Self(inner) = self;
+
+        inner.state.lock().senders == 0
+    }
+}
+
+impl<T> Drop for BorrowedReceiver<'_, T> {
+    fn drop(&mut self) {
+        let Self(inner) = self;
+
+        inner.drop_receiver();
+    }
+}
+
+/// The state of a one-shot channel as observed by [`Receiver::poll_state`].
+///
+/// [`Receiver::poll_state`]: struct.Receiver.html#method.poll_state
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RecvState {
+    /// A value is ready to be received.
+    Ready,
+    /// No value has been sent, but the sender is still connected.
+    Empty,
+    /// The sender was dropped without sending a value, or the value has
+    /// already been taken.
+    Disconnected,
+}
+
+/// A source of the current time for the timed receive operations.
+///
+/// See [`Receiver::recv_timeout_with_clock`].
+///
+/// [`Receiver::recv_timeout_with_clock`]: struct.Receiver.html#method.recv_timeout_with_clock
+pub trait Clock {
+    /// Returns the current time.
+    fn now(&self) -> Instant;
+}
+
+/// A [`Clock`] which reads the monotonic system clock.
+///
+/// [`Clock`]: trait.Clock.html
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// The future returned by [`Sender::send_async`].
+///
+/// [`Sender::send_async`]: struct.Sender.html#method.send_async
+#[cfg(feature = "async")]
+struct SendAsync<T, P> {
+    sender: Sender<T, P>,
+    value: Option<T>,
+}
+
+// The value is never pinned, so the future may be moved freely.
+#[cfg(feature = "async")]
+impl<T, P> Unpin for SendAsync<T, P> {}
+
+#[cfg(feature = "async")]
+impl<T, P> Future for SendAsync<T, P> {
+    type Output = Result<(), SendError<T>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let Self { sender, value } = self.get_mut();
+        let Sender(inner) = sender;
+
+        let mut state = inner.state.lock();
+
+        if let Some(pending) = value.take() {
+            // Rather than blocking the executor for a rendezvous, wait to be
+            // woken once the receiver has parked.
+            if state.rendezvous && !state.waiting && state.receiver && !state.sent {
+                *value = Some(pending);
+                state.sender_waker = Some(cx.waker().clone());
+
+                return Poll::Pending;
+            }
+
+            if let Err(err) = inner.deliver(&mut state, pending) {
+                return Poll::Ready(Err(err));
+            }
+        }
+
+        if state.value.is_none() {
+            return Poll::Ready(Ok(()));
+        }
+
+        if !state.receiver {
+            let value = state.value.take().expect("value is present");
+            inner.publish(&state);
+
+            return Poll::Ready(Err(SendError::Disconnected(value)));
+        }
+
+        state.sender_waker = Some(cx.waker().clone());
+
+        Poll::Pending
+    }
+}
+
+/// A lock-free snapshot of a one-shot channel, as observed by
+/// [`Sender::state`] and [`Receiver::state`].
+///
+/// [`Sender::state`]: struct.Sender.html#method.state
+/// [`Receiver::state`]: struct.Receiver.html#method.state
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ChannelState {
+    /// No value has been sent and both halves are connected.
+    Empty = 0,
+    /// A value has been sent and not yet received.
+    Filled = 1,
+    /// The value was received, or one half was dropped.
+    Disconnected = 2,
+}
+
+/// The error returned by [`Receiver::recv`].
+///
+/// [`Receiver::recv`]: struct.Receiver.html#method.recv
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RecvError {
+    Disconnected,
+    /// The receiver was woken by an [`Interrupt`].
+    ///
+    /// [`Interrupt`]: struct.Interrupt.html
+    Interrupted,
+}
+
+/// The error returned by [`Receiver::recv_timeout`].
 ///
 /// [`Receiver::recv_timeout`]: struct.Receiver.html#method.recv_timeout
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum RecvTimeoutError {
     TimedOut,
     Disconnected,
+    /// The receiver was woken by an [`Interrupt`].
+    ///
+    /// [`Interrupt`]: struct.Interrupt.html
+    Interrupted,
+}
+
+/// Proof that a value was delivered over a one-shot channel.
+///
+/// Tokens are returned by [`Receiver::recv_with_token`].
+///
+/// [`Receiver::recv_with_token`]: struct.Receiver.html#method.recv_with_token
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct DeliveryToken {
+    id: u64,
+    sent_at: Instant,
+}
+
+impl DeliveryToken {
+    /// Returns the identifier of the channel which delivered the value.
+    ///
+    /// See [`channel_with_id`].
+    ///
+    /// [`channel_with_id`]: fn.channel_with_id.html
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns the time at which the value was sent.
+    pub fn sent_at(&self) -> Instant {
+        self.sent_at
+    }
+}
+
+/// The error returned by [`Receiver::try_recv_owned`].
+///
+/// [`Receiver::try_recv_owned`]: struct.Receiver.html#method.try_recv_owned
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TryRecvError {
+    /// No value has been sent, but the sender is still connected.
+    Empty,
+    /// The sender was dropped without sending a value.
+    Disconnected,
+}
+
+/// A handle which interrupts a blocked receiver.
+///
+/// Interrupt handles are created by the [`channel_interruptible`] function.
+/// The handle does not keep the channel alive; triggering it after both halves
+/// have been dropped does nothing.
+///
+/// [`channel_interruptible`]: fn.channel_interruptible.html
+#[derive(Clone)]
+pub struct Interrupt(Weak<dyn Interruptible>);
+
+impl Interrupt {
+    /// Interrupts the receiver.
+    ///
+    /// A receiver blocked in [`recv`] or [`recv_timeout`] is woken and returns
+    /// an `Interrupted` error, as does any later call to either method. A value
+    /// which has already been sent is still received.
+    ///
+    /// [`recv`]: struct.Receiver.html#method.recv
+    /// [`recv_timeout`]: struct.Receiver.html#method.recv_timeout
+    pub fn trigger(&self) {
+        let Self(inner) = self;
+
+        if let Some(inner) = inner.upgrade() {
+            inner.interrupt();
+        }
+    }
+}
+
+impl ::std::fmt::Debug for Interrupt {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        f.debug_struct("Interrupt").finish()
+    }
+}
+
+/// A handle which interrupts many receivers at once.
+///
+/// Channels are registered with the handle when they are created by
+/// [`channel_with_shutdown`]. Like [`Interrupt`] the handle only holds weak
+/// references, so it does not keep any channel alive.
+///
+/// [`channel_with_shutdown`]: fn.channel_with_shutdown.html
+/// [`Interrupt`]: struct.Interrupt.html
+#[derive(Default)]
+pub struct ShutdownHandle(Mutex<Shutdown>);
+
+#[derive(Default)]
+struct Shutdown {
+    triggered: bool,
+    channels: Vec<Weak<dyn Interruptible>>,
+}
+
+impl ShutdownHandle {
+    /// Creates a new shutdown handle with no registered channels.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Interrupts every registered receiver.
+    ///
+    /// Each receiver behaves as if its [`Interrupt`] had been triggered.
+    /// Channels registered after this call are interrupted as soon as they
+    /// are created.
+    ///
+    /// [`Interrupt`]: struct.Interrupt.html
+    pub fn shutdown(&self) {
+        let Self(shutdown) = self;
+        let mut shutdown = shutdown.lock();
+
+        shutdown.triggered = true;
+
+        for inner in shutdown.channels.drain(..) {
+            if let Some(inner) = inner.upgrade() {
+                inner.interrupt();
+            }
+        }
+    }
+
+    /// Returns `true` if [`shutdown`] has been called.
+    ///
+    /// [`shutdown`]: #method.shutdown
+    pub fn is_shutdown(&self) -> bool {
+        let Self(shutdown) = self;
+
+        shutdown.lock().triggered
+    }
+
+    fn register(&self, inner: Weak<dyn Interruptible>) {
+        let Self(shutdown) = self;
+        let mut shutdown = shutdown.lock();
+
+        if shutdown.triggered {
+            if let Some(inner) = inner.upgrade() {
+                inner.interrupt();
+            }
+        } else {
+            // Forget channels which have since been dropped.
+            shutdown.channels.retain(|inner| inner.strong_count() > 0);
+            shutdown.channels.push(inner);
+        }
+    }
+}
+
+impl ::std::fmt::Debug for ShutdownHandle {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        f.debug_struct("ShutdownHandle")
+            .field("triggered", &self.is_shutdown())
+            .finish()
+    }
+}
+
+/// Channel state which can be interrupted without knowing its value type.
+trait Interruptible: Send + Sync {
+    fn interrupt(&self);
+}
+
+impl<T: Send, P: Send> Interruptible for Inner<T, P> {
+    fn interrupt(&self) {
+        let mut state = self.state.lock();
+        state.interrupted = true;
+        self.wake(&state);
+    }
+}
+
+/// Waits on several receivers, which may be of different types, at once.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+/// use std::time::{Duration, Instant};
+///
+/// use nano_oneshot::{Select, SelectResult};
+///
+/// let (s1, r1) = ::nano_oneshot::channel::<u32>();
+/// let (s2, r2) = ::nano_oneshot::channel::<&str>();
+///
+/// let mut select = Select::new();
+/// let t1 = select.add(r1);
+/// let t2 = select.add(r2);
+///
+/// thread::spawn(move || {
+///     let _ = s2.send("hello");
+///     drop(s1);
+/// });
+///
+/// let deadline = Instant::now() + Duration::from_secs(5);
+///
+/// match select.wait_deadline(deadline) {
+///     SelectResult::Ready(token) if token == t2 => {
+///         assert_eq!(select.recv::<&str>(t2).unwrap(), "hello");
+///     }
+///     SelectResult::Ready(token) => assert_eq!(token, t1),
+///     SelectResult::TimedOut => unreachable!(),
+/// }
+/// ```
+#[derive(Default)]
+pub struct Select {
+    signal: Arc<Signal>,
+    entries: Vec<Option<Box<dyn Selectable>>>,
+}
+
+impl Select {
+    /// Creates a new, empty selection.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a receiver to the selection, returning the token that identifies
+    /// it.
+    pub fn add<T: Send + 'static>(&mut self, r: Receiver<T>) -> Token {
+        {
+            let Receiver(inner, _) = &r;
+            inner.state.lock().watcher = Some(Arc::clone(&self.signal));
+        }
+
+        self.entries.push(Some(Box::new(r)));
+
+        Token(self.entries.len() - 1)
+    }
+
+    /// Blocks the current thread until one of the receivers is ready, or
+    /// until `deadline` is reached.
+    ///
+    /// A receiver is ready once a value has been sent or the channel has been
+    /// disconnected, so that [`recv`] will not block. If several receivers
+    /// are ready then the one that was added first is reported.
+    ///
+    /// [`recv`]: #method.recv
+    pub fn wait_deadline(&mut self, deadline: Instant) -> SelectResult {
+        loop {
+            *self.signal.fired.lock() = false;
+
+            if let Some(token) = self.ready() {
+                return SelectResult::Ready(token);
+            }
+
+            let mut fired = self.signal.fired.lock();
+
+            if !*fired
+                && self
+                    .signal
+                    .condvar
+                    .wait_until(&mut fired, deadline)
+                    .timed_out()
+            {
+                drop(fired);
+
+                return match self.ready() {
+                    Some(token) => SelectResult::Ready(token),
+                    None => SelectResult::TimedOut,
+                };
+            }
+        }
+    }
+
+    /// Receives from the receiver identified by `token`, removing it from
+    /// the selection.
+    ///
+    /// This blocks only if the receiver is not yet ready.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `token` did not come from this selection, if its receiver
+    /// has already been removed, or if `T` is not the receiver's value type.
+    pub fn recv<T: 'static>(&mut self, token: Token) -> Result<T, RecvError> {
+        let Token(index) = token;
+
+        let entry = self
+            .entries
+            .get_mut(index)
+            .and_then(Option::take)
+            .expect("receiver is part of the selection");
+
+        match entry.into_any().downcast::<Receiver<T>>() {
+            Ok(r) => r.recv(),
+            Err(_) => panic!("receiver value type does not match"),
+        }
+    }
+
+    fn ready(&self) -> Option<Token> {
+        self.entries
+            .iter()
+            .position(|entry| entry.as_ref().is_some_and(|entry| entry.is_ready()))
+            .map(Token)
+    }
+}
+
+impl ::std::fmt::Debug for Select {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        f.debug_struct("Select")
+            .field("len", &self.entries.iter().filter(|e| e.is_some()).count())
+            .finish()
+    }
+}
+
+/// Identifies a receiver within a [`Select`].
+///
+/// [`Select`]: struct.Select.html
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Token(usize);
+
+/// The result of [`Select::wait_deadline`].
+///
+/// [`Select::wait_deadline`]: struct.Select.html#method.wait_deadline
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SelectResult {
+    /// The identified receiver is ready.
+    Ready(Token),
+    /// The deadline was reached before any receiver was ready.
+    TimedOut,
+}
+
+/// Wakes a [`Select`] when one of its receivers may have become ready.
+///
+/// [`Select`]: struct.Select.html
+#[derive(Debug, Default)]
+struct Signal {
+    fired: Mutex<bool>,
+    condvar: Condvar,
+}
+
+impl Signal {
+    fn fire(&self) {
+        *self.fired.lock() = true;
+        let _ = self.condvar.notify_all();
+    }
+}
+
+/// A receiver which can be waited on by a [`Select`] without knowing its
+/// value type.
+///
+/// [`Select`]: struct.Select.html
+trait Selectable: Send {
+    fn is_ready(&self) -> bool;
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<T: Send + 'static> Selectable for Receiver<T> {
+    fn is_ready(&self) -> bool {
+        self.poll_state() != RecvState::Empty
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
 }
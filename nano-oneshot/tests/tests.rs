@@ -1,8 +1,11 @@
-use std::sync::{Arc, Barrier};
+use std::sync::{mpsc, Arc, Barrier};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use nano_oneshot::{self, RecvError, RecvTimeoutError, SendError};
+use nano_oneshot::{
+    self, ChannelState, Clock, OneshotPool, OneshotSlot, RecvError, RecvState, RecvTimeoutError,
+    Select, SelectResult, SendError, ShutdownHandle, TryRecvError,
+};
 
 #[test]
 fn oneshot() {
@@ -38,6 +41,28 @@ fn oneshot_recv_timeout_drop_sender() {
     );
 }
 
+#[test]
+fn oneshot_recv_timeout_max() {
+    let (s, r) = nano_oneshot::channel();
+    s.send(1).expect("send");
+    assert_eq!(r.recv_timeout(Duration::MAX), Ok(1));
+
+    let (s, r) = nano_oneshot::channel();
+    let handle = thread::spawn(move || r.recv_timeout(Duration::MAX));
+    thread::sleep(Duration::from_millis(10));
+    s.send(2).expect("send");
+    assert_eq!(handle.join().expect("thread"), Ok(2));
+
+    let (s, r) = nano_oneshot::channel::<i32>();
+    let handle = thread::spawn(move || r.recv_timeout(Duration::MAX));
+    thread::sleep(Duration::from_millis(10));
+    drop(s);
+    assert_eq!(
+        handle.join().expect("thread"),
+        Err(RecvTimeoutError::Disconnected)
+    );
+}
+
 #[test]
 fn oneshot_concurrent() {
     let (s, r) = nano_oneshot::channel();
@@ -170,3 +195,968 @@ fn oneshot_concurrent_recv_timeout_drop_sender() {
         RecvTimeoutError::Disconnected
     );
 }
+
+#[test]
+fn oneshot_recv_or() {
+    let (s, r) = nano_oneshot::channel();
+    s.send(128).expect("send");
+    assert_eq!(r.recv_or(0), 128);
+
+    let (s, r) = nano_oneshot::channel();
+    drop(s);
+    assert_eq!(r.recv_or(64), 64);
+}
+
+#[test]
+fn oneshot_recv_or_else() {
+    let (s, r) = nano_oneshot::channel();
+    s.send(128).expect("send");
+    assert_eq!(r.recv_or_else(|| panic!("fallback computed")), 128);
+
+    let (s, r) = nano_oneshot::channel();
+    drop(s);
+    assert_eq!(r.recv_or_else(|| 64), 64);
+}
+
+#[test]
+fn oneshot_recv_or_default() {
+    let (s, r) = nano_oneshot::channel();
+    s.send(128).expect("send");
+    assert_eq!(r.recv_or_default(), 128);
+
+    let (s, r) = nano_oneshot::channel::<i32>();
+    drop(s);
+    assert_eq!(r.recv_or_default(), 0);
+}
+
+#[test]
+fn oneshot_send_back() {
+    let (s, r) = nano_oneshot::channel();
+    assert_eq!(s.send_back(128), Ok(()));
+    assert_eq!(s.send_back(64), Err(64));
+    assert!(!s.is_disconnected());
+    assert_eq!(r.recv().expect("recv"), 128);
+    assert!(s.is_disconnected());
+}
+
+#[test]
+fn oneshot_send_back_drop_receiver() {
+    let (s, r) = nano_oneshot::channel();
+    drop(r);
+    assert_eq!(s.send_back(128), Err(128));
+    assert_eq!(s.send_back(64), Err(64));
+}
+
+#[test]
+fn oneshot_concurrent_send_back() {
+    let (s, r) = nano_oneshot::channel();
+
+    let handle = thread::spawn(move || r.recv());
+
+    assert_eq!(s.send_back(128), Ok(()));
+    assert_eq!(handle.join().expect("thread").expect("recv"), 128);
+}
+
+#[test]
+fn oneshot_forward_to() {
+    let (s, r) = nano_oneshot::channel();
+    let (tx, rx) = mpsc::channel();
+    s.send(128).expect("send");
+    r.forward_to(tx);
+    assert_eq!(rx.recv().expect("recv"), 128);
+
+    let (s, r) = nano_oneshot::channel::<i32>();
+    let (tx, rx) = mpsc::channel();
+    drop(s);
+    r.forward_to(tx);
+    assert!(rx.recv().is_err());
+}
+
+#[test]
+fn oneshot_forward_on_thread() {
+    let (s, r) = nano_oneshot::channel();
+    let (tx, rx) = mpsc::channel();
+
+    let handle = r.forward_on_thread(tx);
+    s.send(128).expect("send");
+
+    handle.join().expect("thread");
+    assert_eq!(rx.recv().expect("recv"), 128);
+}
+
+#[test]
+fn oneshot_poll_state() {
+    let (s, r) = nano_oneshot::channel();
+    assert_eq!(r.poll_state(), RecvState::Empty);
+    s.send(128).expect("send");
+    assert_eq!(r.poll_state(), RecvState::Ready);
+    assert_eq!(r.recv().expect("recv"), 128);
+
+    let (s, r) = nano_oneshot::channel::<i32>();
+    drop(s);
+    assert_eq!(r.poll_state(), RecvState::Disconnected);
+
+    let (s, mut r) = nano_oneshot::channel();
+    assert_eq!(s.send_back(128), Ok(()));
+    assert_eq!(r.take(), Some(128));
+    assert_eq!(r.poll_state(), RecvState::Disconnected);
+}
+
+#[test]
+fn oneshot_spawn() {
+    let r = nano_oneshot::spawn(|| 128);
+    assert_eq!(r.recv().expect("recv"), 128);
+}
+
+#[test]
+fn oneshot_spawn_drop_receiver() {
+    let (s, r) = nano_oneshot::channel();
+
+    drop(nano_oneshot::spawn(move || s.send(128)));
+
+    assert_eq!(r.recv().expect("recv"), 128);
+}
+
+#[test]
+fn oneshot_spawn_panic() {
+    let r = nano_oneshot::spawn(|| -> i32 { panic!("spawned") });
+    assert_eq!(r.recv().unwrap_err(), RecvError::Disconnected);
+}
+
+#[test]
+fn oneshot_progress() {
+    let (s, r) = nano_oneshot::channel_with_progress();
+    assert_eq!(r.poll_progress(), None);
+
+    s.progress(25);
+    assert_eq!(r.poll_progress(), Some(25));
+    assert_eq!(r.poll_progress(), Some(25));
+
+    s.progress(75);
+    assert_eq!(r.poll_progress(), Some(75));
+
+    s.send("done").expect("send");
+    assert_eq!(r.poll_progress(), Some(75));
+    assert_eq!(r.recv().expect("recv"), "done");
+}
+
+#[test]
+fn oneshot_concurrent_progress() {
+    let (s, r) = nano_oneshot::channel_with_progress();
+
+    let handle = thread::spawn(move || {
+        for i in 0..=100 {
+            s.progress(i);
+        }
+        s.send(128)
+    });
+
+    assert_eq!(r.recv().expect("recv"), 128);
+    assert!(handle.join().expect("thread").is_ok());
+}
+
+#[test]
+fn oneshot_recv_now() {
+    let (s, mut r) = nano_oneshot::channel();
+    assert_eq!(r.recv_now(), None);
+    assert_eq!(r.recv_now(), None);
+    s.send(128).expect("send");
+    assert_eq!(r.recv_now(), Some(Ok(128)));
+    assert_eq!(r.recv_now(), Some(Err(RecvError::Disconnected)));
+
+    let (s, mut r) = nano_oneshot::channel();
+    assert_eq!(s.send_back(128), Ok(()));
+    assert_eq!(r.recv_now(), Some(Ok(128)));
+    assert_eq!(s.send_back(64), Err(64));
+    assert_eq!(r.recv_now(), Some(Err(RecvError::Disconnected)));
+
+    let (s, mut r) = nano_oneshot::channel::<i32>();
+    drop(s);
+    assert_eq!(r.recv_now(), Some(Err(RecvError::Disconnected)));
+}
+
+#[test]
+fn oneshot_concurrent_recv_now() {
+    let (s, mut r) = nano_oneshot::channel();
+
+    let handle = thread::spawn(move || s.send(128));
+
+    let value = loop {
+        match r.recv_now() {
+            Some(result) => break result,
+            None => thread::yield_now(),
+        }
+    };
+
+    assert!(handle.join().expect("thread").is_ok());
+    assert_eq!(value, Ok(128));
+}
+
+#[test]
+fn oneshot_interrupt() {
+    let (_s, r, interrupt) = nano_oneshot::channel_interruptible::<i32>();
+    interrupt.trigger();
+    assert_eq!(r.recv(), Err(RecvError::Interrupted));
+
+    let (s, r, interrupt) = nano_oneshot::channel_interruptible();
+    s.send(128).expect("send");
+    interrupt.trigger();
+    assert_eq!(r.recv(), Ok(128));
+
+    let (s, r, interrupt) = nano_oneshot::channel_interruptible::<i32>();
+    drop((s, r));
+    interrupt.trigger();
+}
+
+#[test]
+fn oneshot_concurrent_interrupt() {
+    let (_s, r, interrupt) = nano_oneshot::channel_interruptible::<i32>();
+
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        interrupt.trigger();
+    });
+
+    assert_eq!(
+        r.recv_timeout(Duration::from_secs(5)),
+        Err(RecvTimeoutError::Interrupted)
+    );
+    handle.join().expect("thread");
+}
+
+#[test]
+fn oneshot_channel_in() {
+    let mut slot = OneshotSlot::new();
+
+    let (s, r) = nano_oneshot::channel_in(&mut slot);
+    s.send(128).expect("send");
+    assert_eq!(r.recv(), Ok(128));
+
+    let (s, r) = nano_oneshot::channel_in::<i32>(&mut slot);
+    drop(s);
+    assert!(r.is_disconnected());
+    assert_eq!(r.recv(), Err(RecvError::Disconnected));
+
+    let (s, r) = nano_oneshot::channel_in(&mut slot);
+    drop(r);
+    assert!(s.is_disconnected());
+    assert_eq!(s.send(128).unwrap_err(), SendError::Disconnected(128));
+}
+
+#[test]
+fn oneshot_concurrent_channel_in() {
+    let mut slot = OneshotSlot::new();
+
+    for i in 0..8 {
+        let (s, r) = nano_oneshot::channel_in(&mut slot);
+
+        thread::scope(|scope| {
+            scope.spawn(move || {
+                thread::sleep(Duration::from_millis(1));
+                s.send(i).expect("send");
+            });
+
+            assert_eq!(r.recv_timeout(Duration::from_secs(5)), Ok(i));
+        });
+    }
+}
+
+#[test]
+fn oneshot_concurrent_no_missed_wakeups() {
+    for i in 0..1000 {
+        let (s, r) = nano_oneshot::channel();
+
+        let handle = thread::spawn(move || s.send(i));
+
+        assert_eq!(r.recv_timeout(Duration::from_secs(5)), Ok(i));
+        assert!(handle.join().expect("thread").is_ok());
+    }
+}
+
+#[test]
+fn oneshot_recv_into() {
+    let (s, r) = nano_oneshot::channel();
+    let mut out = None;
+    s.send(128).expect("send");
+    assert_eq!(r.recv_into(&mut out), Ok(()));
+    assert_eq!(out, Some(128));
+
+    let (s, r) = nano_oneshot::channel::<i32>();
+    let mut out = Some(64);
+    drop(s);
+    assert_eq!(r.recv_into(&mut out), Err(RecvError::Disconnected));
+    assert_eq!(out, None);
+}
+
+#[test]
+fn oneshot_must_use() {
+    let (s, r) = nano_oneshot::channel();
+    let r = r.must_use();
+    s.send(128).expect("send");
+    assert_eq!(r.recv(), Ok(128));
+
+    let (s, r) = nano_oneshot::channel::<i32>();
+    let r = r.must_use();
+    drop(s);
+    drop(r);
+
+    let (s, r) = nano_oneshot::channel();
+    let r = r.must_use().into_inner();
+    s.send(128).expect("send");
+    drop(r);
+}
+
+#[test]
+#[should_panic(expected = "never received")]
+fn oneshot_must_use_unread() {
+    let (s, r) = nano_oneshot::channel();
+    let r = r.must_use();
+    s.send(128).expect("send");
+    drop(r);
+}
+
+#[test]
+fn oneshot_must_use_on_unread() {
+    let (tx, rx) = mpsc::channel();
+
+    let (s, r) = nano_oneshot::channel();
+    let r = r.must_use().on_unread(move || tx.send(()).expect("send"));
+    s.send(128).expect("send");
+    drop(r);
+
+    assert_eq!(rx.try_recv(), Ok(()));
+}
+
+#[test]
+fn oneshot_into_result_iter() {
+    let (s, r) = nano_oneshot::channel();
+    s.send(128).expect("send");
+    assert_eq!(r.into_result_iter().collect::<Vec<_>>(), [Ok(128)]);
+
+    let (s, r) = nano_oneshot::channel::<i32>();
+    drop(s);
+    assert_eq!(
+        r.into_result_iter().collect::<Vec<_>>(),
+        [Err(RecvError::Disconnected)]
+    );
+}
+
+#[test]
+fn oneshot_race() {
+    let (s1, s2, r) = nano_oneshot::channel_race();
+    s1.send(128).expect("send");
+    assert_eq!(s2.send(64), Err(SendError::AlreadySent(64)));
+    assert_eq!(r.recv(), Ok(128));
+
+    let (s1, s2, r) = nano_oneshot::channel_race();
+    drop(s1);
+    assert!(!r.is_disconnected());
+    s2.send(64).expect("send");
+    assert_eq!(r.recv(), Ok(64));
+
+    let (s1, s2, r) = nano_oneshot::channel_race::<i32>();
+    drop(s1);
+    drop(s2);
+    assert_eq!(r.recv(), Err(RecvError::Disconnected));
+}
+
+#[test]
+fn oneshot_concurrent_race() {
+    let (s1, s2, r) = nano_oneshot::channel_race();
+    let barrier = Arc::new(Barrier::new(2));
+
+    let handles: Vec<_> = vec![(s1, 1), (s2, 2)]
+        .into_iter()
+        .map(|(s, i)| {
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                barrier.wait();
+                s.send(i)
+            })
+        })
+        .collect();
+
+    let value = r.recv().expect("recv");
+    let results: Vec<_> = handles
+        .into_iter()
+        .map(|h| h.join().expect("thread"))
+        .collect();
+
+    assert!(results.contains(&Ok(())));
+    assert!(results.contains(&Err(SendError::AlreadySent(3 - value))));
+}
+
+#[test]
+fn oneshot_select() {
+    let (s1, r1) = nano_oneshot::channel::<i32>();
+    let (s2, r2) = nano_oneshot::channel::<&str>();
+    let (s3, r3) = nano_oneshot::channel::<char>();
+
+    let mut select = Select::new();
+    let t1 = select.add(r1);
+    let t2 = select.add(r2);
+    let t3 = select.add(r3);
+
+    let soon = || Instant::now() + Duration::from_millis(10);
+
+    assert_eq!(select.wait_deadline(soon()), SelectResult::TimedOut);
+
+    s2.send("hello").expect("send");
+    assert_eq!(select.wait_deadline(soon()), SelectResult::Ready(t2));
+    assert_eq!(select.recv::<&str>(t2), Ok("hello"));
+
+    drop(s3);
+    assert_eq!(select.wait_deadline(soon()), SelectResult::Ready(t3));
+    assert_eq!(select.recv::<char>(t3), Err(RecvError::Disconnected));
+
+    assert_eq!(select.wait_deadline(soon()), SelectResult::TimedOut);
+    s1.send(128).expect("send");
+    assert_eq!(select.wait_deadline(soon()), SelectResult::Ready(t1));
+    assert_eq!(select.recv::<i32>(t1), Ok(128));
+}
+
+#[test]
+#[should_panic(expected = "does not match")]
+fn oneshot_select_wrong_type() {
+    let (_s, r) = nano_oneshot::channel::<i32>();
+
+    let mut select = Select::new();
+    let token = select.add(r);
+    let _ = select.recv::<u32>(token);
+}
+
+#[test]
+fn oneshot_concurrent_select() {
+    let (s1, r1) = nano_oneshot::channel::<i32>();
+    let (s2, r2) = nano_oneshot::channel::<String>();
+
+    let mut select = Select::new();
+    let _t1 = select.add(r1);
+    let t2 = select.add(r2);
+
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        s2.send("hello".to_string()).expect("send");
+        s1
+    });
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    assert_eq!(select.wait_deadline(deadline), SelectResult::Ready(t2));
+    assert_eq!(select.recv::<String>(t2).as_deref(), Ok("hello"));
+
+    drop(handle.join().expect("thread"));
+}
+
+#[test]
+fn oneshot_recv_peeking() {
+    let (s, mut r) = nano_oneshot::channel();
+    s.send(128).expect("send");
+    assert_eq!(r.recv_peeking(), Ok(&128));
+    assert_eq!(r.recv_peeking(), Ok(&128));
+    assert_eq!(r.poll_state(), RecvState::Ready);
+    assert_eq!(r.recv(), Ok(128));
+
+    let (s, mut r) = nano_oneshot::channel();
+    assert_eq!(r.take(), None);
+    s.send(128).expect("send");
+    assert_eq!(r.take(), Some(128));
+    assert_eq!(r.take(), None);
+    assert_eq!(r.recv_peeking(), Err(RecvError::Disconnected));
+
+    let (s, mut r) = nano_oneshot::channel::<i32>();
+    drop(s);
+    assert_eq!(r.recv_peeking(), Err(RecvError::Disconnected));
+}
+
+#[test]
+fn oneshot_concurrent_recv_peeking() {
+    let (s, mut r) = nano_oneshot::channel();
+
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        s.send(128)
+    });
+
+    assert_eq!(r.recv_peeking(), Ok(&128));
+    assert!(handle.join().expect("thread").is_ok());
+    assert_eq!(r.recv_now(), Some(Ok(128)));
+}
+
+#[test]
+fn oneshot_state_tracks_slot() {
+    let (s, mut r) = nano_oneshot::channel();
+    assert_eq!(s.state(), ChannelState::Empty);
+    assert_eq!(r.state(), ChannelState::Empty);
+
+    s.send(1).expect("send");
+    assert_eq!(r.state(), ChannelState::Filled);
+
+    assert_eq!(r.recv_peeking(), Ok(&1));
+    assert_eq!(r.state(), ChannelState::Filled);
+
+    assert_eq!(r.take(), Some(1));
+    assert_eq!(r.state(), ChannelState::Disconnected);
+
+    let (s, r) = nano_oneshot::channel::<i32>();
+    drop(s);
+    assert_eq!(r.state(), ChannelState::Disconnected);
+
+    let (s, r) = nano_oneshot::channel::<i32>();
+    drop(r);
+    assert_eq!(s.state(), ChannelState::Disconnected);
+}
+
+#[test]
+fn oneshot_recv_timeout_with_clock() {
+    let hour = Duration::from_secs(3600);
+
+    // A clock which advances by a fixed step every time it is read.
+    struct Expiring(std::sync::Mutex<Instant>, Duration);
+
+    impl Clock for Expiring {
+        fn now(&self) -> Instant {
+            let mut now = self.0.lock().expect("lock");
+            let then = *now;
+            *now += self.1;
+            then
+        }
+    }
+
+    let (_s, r) = nano_oneshot::channel::<i32>();
+    let clock = Expiring(std::sync::Mutex::new(Instant::now()), hour);
+    let start = Instant::now();
+    assert_eq!(
+        r.recv_timeout_with_clock(hour, &clock),
+        Err(RecvTimeoutError::TimedOut)
+    );
+    assert!(start.elapsed() < hour);
+
+    let (s, r) = nano_oneshot::channel();
+    s.send(1).expect("send");
+    let clock = Expiring(
+        std::sync::Mutex::new(Instant::now()),
+        Duration::from_secs(0),
+    );
+    assert_eq!(
+        r.recv_timeout_with_clock(Duration::from_secs(0), &clock),
+        Ok(1)
+    );
+
+    // A frozen clock never expires, but the receiver is still woken by a send.
+    let (s, r) = nano_oneshot::channel();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        s.send(2).expect("send");
+    });
+    assert_eq!(r.recv_timeout_with_clock(hour, &clock), Ok(2));
+    handle.join().expect("thread");
+}
+
+#[test]
+fn oneshot_receiver_is_waiting() {
+    let (s, r) = nano_oneshot::channel();
+    assert!(!s.receiver_is_waiting());
+
+    let handle = thread::spawn(move || r.recv_timeout(Duration::from_secs(60)));
+
+    while !s.receiver_is_waiting() {
+        thread::sleep(Duration::from_millis(1));
+    }
+
+    s.send(1).expect("send");
+    assert_eq!(handle.join().expect("thread"), Ok(1));
+}
+
+#[test]
+fn oneshot_recv_timed() {
+    let (s, r) = nano_oneshot::channel();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        s.send(1).expect("send");
+    });
+
+    let (result, waited) = r.recv_timed();
+    assert_eq!(result, Ok(1));
+    assert!(waited >= Duration::from_millis(20));
+    handle.join().expect("thread");
+
+    let (s, r) = nano_oneshot::channel::<i32>();
+    drop(s);
+    assert_eq!(r.recv_timed().0, Err(RecvError::Disconnected));
+}
+
+#[test]
+fn oneshot_try_recv_owned() {
+    let (s, r) = nano_oneshot::channel();
+
+    let r = match r.try_recv_owned() {
+        Err((r, TryRecvError::Empty)) => r,
+        _ => panic!("expected an empty channel"),
+    };
+
+    s.send(1).expect("send");
+    assert_eq!(r.try_recv_owned().ok(), Some(1));
+
+    let (s, r) = nano_oneshot::channel::<i32>();
+    drop(s);
+
+    match r.try_recv_owned() {
+        Err((r, TryRecvError::Disconnected)) => assert!(r.is_disconnected()),
+        _ => panic!("expected a disconnected channel"),
+    }
+}
+
+#[test]
+fn oneshot_rendezvous() {
+    let (s, r) = nano_oneshot::channel_rendezvous();
+    let (tx, rx) = mpsc::channel();
+
+    let handle = thread::spawn(move || {
+        let result = s.send(1);
+        tx.send(()).expect("send");
+        result
+    });
+
+    // The sender stays blocked while the receiver is not parked.
+    thread::sleep(Duration::from_millis(20));
+    assert!(rx.try_recv().is_err());
+
+    assert_eq!(r.recv(), Ok(1));
+    assert_eq!(handle.join().expect("thread"), Ok(()));
+
+    let (s, r) = nano_oneshot::channel_rendezvous();
+    let handle = thread::spawn(move || s.send(2));
+
+    thread::sleep(Duration::from_millis(20));
+    drop(r);
+    assert_eq!(
+        handle.join().expect("thread"),
+        Err(SendError::Disconnected(2))
+    );
+}
+
+#[test]
+fn oneshot_rendezvous_send_timeout() {
+    let (s, r) = nano_oneshot::channel_rendezvous();
+    let start = Instant::now();
+    assert_eq!(
+        s.send_timeout(1, Duration::from_millis(20)),
+        Err(SendError::TimedOut(1))
+    );
+    assert!(start.elapsed() >= Duration::from_millis(20));
+    assert!(r.is_disconnected());
+
+    let (s, r) = nano_oneshot::channel_rendezvous();
+    let handle = thread::spawn(move || s.send_timeout(2, Duration::from_secs(10)));
+    assert_eq!(r.recv(), Ok(2));
+    assert_eq!(handle.join().expect("thread"), Ok(()));
+
+    let (s, r) = nano_oneshot::channel_rendezvous();
+    let handle = thread::spawn(move || s.send_timeout(3, Duration::from_secs(10)));
+    thread::sleep(Duration::from_millis(20));
+    drop(r);
+    assert_eq!(
+        handle.join().expect("thread"),
+        Err(SendError::Disconnected(3))
+    );
+
+    // Other channels never wait for the receiver.
+    let (s, r) = nano_oneshot::channel();
+    assert_eq!(s.send_timeout(4, Duration::from_secs(0)), Ok(()));
+    assert_eq!(r.recv(), Ok(4));
+}
+
+#[test]
+fn oneshot_rendezvous_send_timeout_max() {
+    let (s, r) = nano_oneshot::channel_rendezvous();
+    let handle = thread::spawn(move || s.send_timeout(1, Duration::MAX));
+    thread::sleep(Duration::from_millis(10));
+    assert_eq!(r.recv(), Ok(1));
+    assert_eq!(handle.join().expect("thread"), Ok(()));
+
+    let (s, r) = nano_oneshot::channel();
+    assert_eq!(s.send_timeout(2, Duration::MAX), Ok(()));
+    assert_eq!(r.recv(), Ok(2));
+}
+
+#[test]
+fn oneshot_channel_ids() {
+    let (s, r) = nano_oneshot::channel_with_id::<i32>(7);
+    assert_eq!((s.id(), r.id()), (7, 7));
+
+    let (s0, r0) = nano_oneshot::channel::<i32>();
+    let (s1, r1) = nano_oneshot::channel::<i32>();
+    assert_eq!(s0.id(), r0.id());
+    assert_eq!(s1.id(), r1.id());
+    assert!(s1.id() > s0.id());
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn oneshot_send_async() {
+    use std::future::Future;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    struct Flag(std::sync::atomic::AtomicBool);
+
+    impl Wake for Flag {
+        fn wake(self: Arc<Self>) {
+            self.0.store(true, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+
+    let flag = Arc::new(Flag(Default::default()));
+    let waker = Waker::from(Arc::clone(&flag));
+    let mut cx = Context::from_waker(&waker);
+
+    let (s, r) = nano_oneshot::channel();
+    let mut fut = Box::pin(s.send_async(1));
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+
+    let handle = thread::spawn(move || r.recv());
+    assert_eq!(handle.join().expect("thread"), Ok(1));
+    assert!(flag.0.load(std::sync::atomic::Ordering::SeqCst));
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(Ok(())));
+
+    // The value is returned if the receiver is dropped without taking it.
+    let (s, r) = nano_oneshot::channel();
+    let mut fut = Box::pin(s.send_async(2));
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+    drop(r);
+    assert_eq!(
+        fut.as_mut().poll(&mut cx),
+        Poll::Ready(Err(SendError::Disconnected(2)))
+    );
+
+    let (s, r) = nano_oneshot::channel();
+    drop(r);
+    let mut fut = Box::pin(s.send_async(3));
+    assert_eq!(
+        fut.as_mut().poll(&mut cx),
+        Poll::Ready(Err(SendError::Disconnected(3)))
+    );
+
+    // A rendezvous is waited for without blocking the poll.
+    flag.0.store(false, std::sync::atomic::Ordering::SeqCst);
+    let (s, r) = nano_oneshot::channel_rendezvous();
+    let mut fut = Box::pin(s.send_async(4));
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+    assert_eq!(r.state(), ChannelState::Empty);
+
+    let handle = thread::spawn(move || r.recv());
+
+    while !flag.0.load(std::sync::atomic::Ordering::SeqCst) {
+        thread::yield_now();
+    }
+
+    flag.0.store(false, std::sync::atomic::Ordering::SeqCst);
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+    assert_eq!(handle.join().expect("thread"), Ok(4));
+    assert!(flag.0.load(std::sync::atomic::Ordering::SeqCst));
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(Ok(())));
+}
+
+#[test]
+fn oneshot_shutdown_handle() {
+    let handle = ShutdownHandle::new();
+    let mut senders = Vec::new();
+    let mut threads = Vec::new();
+
+    for _ in 0..8 {
+        let (s, r) = nano_oneshot::channel_with_shutdown::<i32>(&handle);
+        senders.push(s);
+        threads.push(thread::spawn(move || r.recv()));
+    }
+
+    // A channel which has already delivered its value is unaffected.
+    let (s, r) = nano_oneshot::channel_with_shutdown(&handle);
+    s.send(1).expect("send");
+
+    thread::sleep(Duration::from_millis(20));
+    handle.shutdown();
+    assert!(handle.is_shutdown());
+
+    for thread in threads {
+        assert_eq!(thread.join().expect("thread"), Err(RecvError::Interrupted));
+    }
+    assert_eq!(r.recv(), Ok(1));
+
+    let (_s, r) = nano_oneshot::channel_with_shutdown::<i32>(&handle);
+    assert_eq!(r.recv(), Err(RecvError::Interrupted));
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn oneshot_recv_future_cancel_safe() {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll, Waker};
+
+    let mut cx = Context::from_waker(Waker::noop());
+
+    let (s, mut r) = nano_oneshot::channel();
+
+    // A future which is polled once and then dropped before the value
+    // arrives, as by a losing `select!` branch.
+    {
+        let mut fut = Box::pin(&mut r);
+        assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+        s.send(1).expect("send");
+    }
+
+    assert_eq!(r.poll_state(), RecvState::Ready);
+
+    let fut = &mut r;
+    assert_eq!(Pin::new(fut).poll(&mut cx), Poll::Ready(Ok(1)));
+    assert_eq!(
+        r.poll_recv(&mut cx),
+        Poll::Ready(Err(RecvError::Disconnected))
+    );
+
+    let (s, mut r) = nano_oneshot::channel::<i32>();
+    assert_eq!(r.poll_recv(&mut cx), Poll::Pending);
+    drop(s);
+    assert_eq!(
+        Pin::new(&mut r).poll(&mut cx),
+        Poll::Ready(Err(RecvError::Disconnected))
+    );
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn oneshot_recv_future_wakes() {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::task::{Context, Poll, Wake, Waker};
+
+    struct Flag(AtomicBool);
+
+    impl Wake for Flag {
+        fn wake(self: Arc<Self>) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    let flag = Arc::new(Flag(AtomicBool::new(false)));
+    let waker = Waker::from(Arc::clone(&flag));
+    let mut cx = Context::from_waker(&waker);
+
+    let (s, mut r) = nano_oneshot::channel();
+    assert_eq!(Pin::new(&mut r).poll(&mut cx), Poll::Pending);
+
+    thread::spawn(move || s.send(1))
+        .join()
+        .expect("thread")
+        .expect("send");
+
+    assert!(flag.0.load(Ordering::SeqCst));
+    assert_eq!(Pin::new(&mut r).poll(&mut cx), Poll::Ready(Ok(1)));
+}
+
+#[test]
+fn oneshot_recv_with_token() {
+    let (s, r) = nano_oneshot::channel_with_id(3);
+    let before = Instant::now();
+    s.send(1).expect("send");
+    let after = Instant::now();
+
+    thread::sleep(Duration::from_millis(10));
+
+    let (value, token) = r.recv_with_token().expect("recv");
+    assert_eq!(value, 1);
+    assert_eq!(token.id(), 3);
+    assert!(before <= token.sent_at() && token.sent_at() <= after);
+    assert!(token.sent_at().elapsed() >= Duration::from_millis(10));
+
+    let (s, r) = nano_oneshot::channel::<i32>();
+    drop(s);
+    assert_eq!(r.recv_with_token(), Err(RecvError::Disconnected));
+}
+
+#[test]
+fn oneshot_pool() {
+    let pool = OneshotPool::new();
+    assert_eq!(pool.idle(), 0);
+
+    let (s, r) = pool.channel();
+    let id = s.id();
+    s.send(1).expect("send");
+    assert_eq!(r.recv().expect("recv"), 1);
+    assert_eq!(pool.idle(), 1);
+
+    let (s, r) = pool.channel();
+    assert_eq!(pool.idle(), 0);
+    assert_ne!(s.id(), id);
+    assert_eq!(r.state(), ChannelState::Empty);
+    thread::spawn(move || s.send(2))
+        .join()
+        .expect("thread")
+        .expect("send");
+    assert_eq!(r.recv().expect("recv"), 2);
+
+    let (s, r) = pool.channel();
+    drop(pool);
+    drop(s);
+    assert_eq!(r.recv(), Err(RecvError::Disconnected));
+}
+
+#[test]
+fn oneshot_pool_drops_unreceived_value() {
+    let pool = OneshotPool::new();
+    let value = Arc::new(());
+
+    let (s, r) = pool.channel();
+    s.send(Arc::clone(&value)).expect("send");
+    drop(r);
+    assert_eq!(Arc::strong_count(&value), 1);
+    assert_eq!(pool.idle(), 1);
+}
+
+#[cfg(feature = "debug-wakeups")]
+#[test]
+fn oneshot_spurious_wakeups() {
+    let (s, r) = nano_oneshot::channel();
+    assert_eq!(r.spurious_wakeups(), 0);
+
+    let t = thread::spawn(move || r.recv_with_wakeups());
+
+    while !s.receiver_is_waiting() {
+        thread::yield_now();
+    }
+
+    s.__wake_receiver();
+
+    // Give the receiver time to count the wakeup and park again.
+    thread::sleep(Duration::from_millis(10));
+    s.send(1).expect("send");
+    assert_eq!(t.join().expect("thread"), (Ok(1), 1));
+
+    let (s, mut r) = nano_oneshot::channel::<i32>();
+    let t = thread::spawn(move || {
+        let _ = s.send(1);
+    });
+    assert_eq!(r.recv_peeking(), Ok(&1));
+    assert_eq!(r.spurious_wakeups(), 0);
+    t.join().expect("thread");
+}
+
+#[test]
+fn oneshot_then_spawn() {
+    let (s, r) = nano_oneshot::channel();
+    let r = r.then_spawn(|n: i32| n + 1).then_spawn(|n| n * 2);
+    s.send(1).expect("send");
+    assert_eq!(r.recv(), Ok(4));
+
+    let ran = Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let flag = Arc::clone(&ran);
+    let (s, r) = nano_oneshot::channel::<i32>();
+    let r = r.then_spawn(move |n| {
+        flag.store(true, std::sync::atomic::Ordering::SeqCst);
+        n
+    });
+    drop(s);
+    assert_eq!(r.recv(), Err(RecvError::Disconnected));
+    assert!(!ran.load(std::sync::atomic::Ordering::SeqCst));
+}